@@ -0,0 +1,38 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use graph_cycles::testing::{
+    deep_component, dense_random_graph, many_small_components, random_graph,
+};
+use graph_cycles::Cycles;
+
+fn cycles(c: &mut Criterion) {
+    let dense = dense_random_graph(12, 3);
+    let small = many_small_components(1000, 5, 0);
+    let large_then_small = many_small_components(100_000, 2, 200_000);
+    let deep = deep_component(5000);
+    let sparse = random_graph(2000, 20000);
+
+    let mut group = c.benchmark_group("cycles");
+    group.bench_function("dense random SCC", |b| {
+        b.iter(|| black_box(&dense).count_cycles())
+    });
+    group.bench_function("many small SCCs", |b| {
+        b.iter(|| black_box(&small).count_cycles())
+    });
+    group.bench_function("large SCC, then many small SCCs", |b| {
+        b.iter(|| black_box(&large_then_small).count_cycles())
+    });
+    group.bench_function("long chain SCC", |b| {
+        b.iter(|| black_box(&deep).count_cycles())
+    });
+    #[cfg(feature = "rayon")]
+    group.bench_function("long chain SCC, parallel", |b| {
+        b.iter(|| black_box(&deep).par_cycles_within_scc().len())
+    });
+    group.bench_function("triangles", |b| {
+        b.iter(|| black_box(&sparse).triangles().len())
+    });
+    group.finish();
+}
+
+criterion_group!(benches, cycles);
+criterion_main!(benches);
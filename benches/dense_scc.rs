@@ -0,0 +1,63 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use graph_cycles::testing::dense_random_graph;
+use graph_cycles::{CycleFinder, CycleSearchOptions, Cycles};
+use std::ops::ControlFlow;
+
+fn dense_scc(c: &mut Criterion) {
+    let g = dense_random_graph(200, 10);
+    let options = CycleSearchOptions::new().max_len(4);
+    c.bench_function("dense SCC, cycles up to length 4", |b| {
+        b.iter(|| {
+            let mut count = 0;
+            black_box(&g).visit_cycles_with(&options, |_, _| {
+                count += 1;
+                ControlFlow::<()>::Continue(())
+            });
+            count
+        })
+    });
+}
+
+// compare hash sets and bit sets for the B-lists of Johnson's algorithm
+fn blists(c: &mut Criterion) {
+    let small = dense_random_graph(12, 3);
+    let large = dense_random_graph(200, 10);
+    let mut group = c.benchmark_group("B-lists");
+    for (name, dense) in [("hash sets", false), ("bit sets", true)] {
+        group.bench_function(format!("small dense SCC, {name}"), |b| {
+            b.iter(|| {
+                let g = black_box(&small);
+                let mut finder =
+                    CycleFinder::new(g, g.node_indices().collect());
+                finder.set_dense_blists(Some(dense));
+                let mut count = 0;
+                while finder.next_cycle().is_some() {
+                    count += 1;
+                }
+                count
+            })
+        });
+        group.bench_function(format!("large dense SCC, {name}"), |b| {
+            b.iter(|| {
+                let g = black_box(&large);
+                let mut finder =
+                    CycleFinder::new(g, g.node_indices().collect());
+                finder.set_dense_blists(Some(dense));
+                let mut count = 0;
+                let _ = finder.visit(|_| {
+                    count += 1;
+                    if count < 100_000 {
+                        ControlFlow::Continue(())
+                    } else {
+                        ControlFlow::Break(())
+                    }
+                });
+                count
+            })
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, dense_scc, blists);
+criterion_main!(benches);
@@ -0,0 +1,102 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use graph_cycles::testing::{deep_component, many_small_components};
+use graph_cycles::Cycles;
+use petgraph::{algo::tarjan_scc, graph::Graph};
+
+// Johnson's algorithm with native recursion, as a baseline for the
+// iterative search. In contrast to the latter, it overflows the stack
+// for long enough paths.
+fn count_cycles_recursive(g: &Graph<(), ()>) -> usize {
+    struct Search {
+        adjacent: Vec<Vec<usize>>,
+        blocked: Vec<bool>,
+        b: Vec<Vec<usize>>,
+        num_cycles: usize,
+    }
+
+    impl Search {
+        fn unblock(&mut self, v: usize) {
+            self.blocked[v] = false;
+            while let Some(w) = self.b[v].pop() {
+                if self.blocked[w] {
+                    self.unblock(w);
+                }
+            }
+        }
+
+        fn circuit(&mut self, s: usize, v: usize) -> bool {
+            let mut f = false;
+            self.blocked[v] = true;
+            for i in 0..self.adjacent[v].len() {
+                let w = self.adjacent[v][i];
+                if w < s {
+                    continue;
+                }
+                if w == s {
+                    self.num_cycles += 1;
+                    f = true;
+                } else if !self.blocked[w] && self.circuit(s, w) {
+                    f = true;
+                }
+            }
+            if f {
+                self.unblock(v);
+            } else {
+                for i in 0..self.adjacent[v].len() {
+                    let w = self.adjacent[v][i];
+                    if w >= s && !self.b[w].contains(&v) {
+                        self.b[w].push(v);
+                    }
+                }
+            }
+            f
+        }
+    }
+
+    let mut num_cycles = 0;
+    for component in tarjan_scc(g) {
+        let pos = |n| component.iter().position(|&m| m == n);
+        let adjacent = component
+            .iter()
+            .map(|&v| g.neighbors(v).filter_map(pos).collect())
+            .collect();
+        let mut search = Search {
+            adjacent,
+            blocked: vec![false; component.len()],
+            b: vec![Vec::new(); component.len()],
+            num_cycles: 0,
+        };
+        for s in 0..component.len() {
+            search.blocked.fill(false);
+            search.b.iter_mut().for_each(Vec::clear);
+            search.circuit(s, s);
+        }
+        num_cycles += search.num_cycles;
+    }
+    num_cycles
+}
+
+fn recursion(c: &mut Criterion) {
+    let small = many_small_components(1000, 5, 0);
+    let deep = deep_component(5000);
+    assert_eq!(count_cycles_recursive(&small), small.count_cycles());
+    assert_eq!(count_cycles_recursive(&deep), deep.count_cycles());
+
+    let mut group = c.benchmark_group("recursion");
+    group.bench_function("many small SCCs, iterative", |b| {
+        b.iter(|| black_box(&small).count_cycles())
+    });
+    group.bench_function("many small SCCs, recursive", |b| {
+        b.iter(|| count_cycles_recursive(black_box(&small)))
+    });
+    group.bench_function("deep SCC, iterative", |b| {
+        b.iter(|| black_box(&deep).count_cycles())
+    });
+    group.bench_function("deep SCC, recursive", |b| {
+        b.iter(|| count_cycles_recursive(black_box(&deep)))
+    });
+    group.finish();
+}
+
+criterion_group!(benches, recursion);
+criterion_main!(benches);
@@ -0,0 +1,101 @@
+use alloc::collections::VecDeque;
+use alloc::{vec, vec::Vec};
+
+use petgraph::visit::{IntoNeighbors, IntoNodeIdentifiers, NodeIndexable};
+
+/// Compute a fundamental cycle basis of the underlying undirected
+/// simple graph
+///
+/// We build a breadth-first spanning forest and return one cycle for
+/// each edge that is not part of it. Edge directions are ignored and
+/// parallel edges are merged, so a self-loop is the only cycle that
+/// can have fewer than three nodes.
+pub(crate) fn fundamental_cycle_basis<G>(graph: G) -> Vec<Vec<G::NodeId>>
+where
+    G: IntoNeighbors + IntoNodeIdentifiers + NodeIndexable,
+{
+    // undirected neighbours without duplicates, keyed by
+    // `NodeIndexable::to_index`
+    let mut neighbors = vec![Vec::new(); graph.node_bound()];
+    let mut self_loops = Vec::new();
+    for n in graph.node_identifiers() {
+        let u = graph.to_index(n);
+        for v in graph.neighbors(n).map(|n| graph.to_index(n)) {
+            if u == v {
+                self_loops.push(u);
+            } else {
+                neighbors[u].push(v);
+                neighbors[v].push(u);
+            }
+        }
+    }
+    for neighbors in &mut neighbors {
+        neighbors.sort_unstable();
+        neighbors.dedup();
+    }
+    self_loops.dedup();
+
+    let mut cycles: Vec<_> = self_loops
+        .into_iter()
+        .map(|u| vec![graph.from_index(u)])
+        .collect();
+
+    // breadth-first spanning forest
+    let mut parent = vec![usize::MAX; graph.node_bound()];
+    let mut depth = vec![0; graph.node_bound()];
+    let mut is_reached = vec![false; graph.node_bound()];
+    let mut queue = VecDeque::new();
+    let mut order = Vec::new();
+    for n in graph.node_identifiers() {
+        let root = graph.to_index(n);
+        if is_reached[root] {
+            continue;
+        }
+        is_reached[root] = true;
+        queue.push_back(root);
+        while let Some(u) = queue.pop_front() {
+            order.push(u);
+            for &v in &neighbors[u] {
+                if !is_reached[v] {
+                    is_reached[v] = true;
+                    parent[v] = u;
+                    depth[v] = depth[u] + 1;
+                    queue.push_back(v);
+                }
+            }
+        }
+    }
+
+    // one cycle for each edge outside the forest
+    let mut tail = Vec::new();
+    for &u in &order {
+        for &v in neighbors[u].iter().filter(|&&v| v > u) {
+            if parent[u] == v || parent[v] == u {
+                continue;
+            }
+            // walk up from both ends until the paths meet
+            let mut cycle = Vec::new();
+            tail.clear();
+            let (mut a, mut b) = (u, v);
+            while depth[a] > depth[b] {
+                cycle.push(a);
+                a = parent[a];
+            }
+            while depth[b] > depth[a] {
+                tail.push(b);
+                b = parent[b];
+            }
+            while a != b {
+                cycle.push(a);
+                tail.push(b);
+                a = parent[a];
+                b = parent[b];
+            }
+            cycle.push(a);
+            cycle.extend(tail.iter().rev());
+            cycles
+                .push(cycle.into_iter().map(|n| graph.from_index(n)).collect());
+        }
+    }
+    cycles
+}
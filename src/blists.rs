@@ -0,0 +1,132 @@
+use alloc::{vec, vec::Vec};
+use core::hash::BuildHasher;
+
+use fixedbitset::FixedBitSet;
+
+use crate::hash::HashSet;
+
+/// The B-lists from Johnson's algorithm
+///
+/// For each vertex `w`, the B-list contains the blocked vertices that
+/// have to be unblocked together with `w`. Each entry is a direct
+/// predecessor of `w`. For dense components we store each list as a
+/// bit set, which is faster and, if there are sufficiently many
+/// edges, also takes less memory than a hash set. The hash sets use
+/// the hasher `S`.
+#[derive(Clone, Debug)]
+pub(crate) enum BLists<S = ahash::RandomState> {
+    Sparse(Vec<HashSet<usize, S>>),
+    Dense(Vec<FixedBitSet>),
+}
+
+impl<S: BuildHasher + Default> BLists<S> {
+    /// B-lists for a component with the given number of vertices,
+    /// stored as bit sets if `dense` is true
+    pub(crate) fn new(num_vertices: usize, dense: bool) -> Self {
+        if dense {
+            Self::Dense(vec![
+                FixedBitSet::with_capacity(num_vertices);
+                num_vertices
+            ])
+        } else {
+            let b = (0..num_vertices).map(|_| HashSet::default()).collect();
+            Self::Sparse(b)
+        }
+    }
+
+    /// Prepare empty B-lists for a new component
+    ///
+    /// If possible, the existing allocations are reused.
+    pub(crate) fn reset(&mut self, num_vertices: usize, num_edges: usize) {
+        let dense = Self::prefer_dense(num_vertices, num_edges);
+        self.reset_as(num_vertices, dense);
+    }
+
+    /// Prepare empty B-lists for a new component, stored as bit sets
+    /// if `dense` is true
+    ///
+    /// Only the B-lists of the first `num_vertices` vertices are
+    /// cleared. The remaining ones are cleared before they are used
+    /// for a larger component.
+    pub(crate) fn reset_as(&mut self, num_vertices: usize, dense: bool) {
+        match self {
+            Self::Sparse(b) if !dense => {
+                if b.len() < num_vertices {
+                    b.resize_with(num_vertices, HashSet::default);
+                }
+                for b in &mut b[..num_vertices] {
+                    b.clear();
+                }
+            }
+            Self::Dense(b) if dense => {
+                if b.len() < num_vertices {
+                    b.resize_with(num_vertices, FixedBitSet::new);
+                }
+                for b in &mut b[..num_vertices] {
+                    // clearing a bit set from a larger component would
+                    // take longer than creating a new one
+                    if b.len() > num_vertices {
+                        *b = FixedBitSet::with_capacity(num_vertices);
+                    } else {
+                        b.clear();
+                        b.grow(num_vertices);
+                    }
+                }
+            }
+            _ => *self = Self::new(num_vertices, dense),
+        }
+    }
+
+    fn prefer_dense(num_vertices: usize, num_edges: usize) -> bool {
+        // A bit set needs `num_vertices` bits, whereas a hash set
+        // needs at least about 16 bytes per entry. Since the entries
+        // of all B-lists are distinct edges, they cannot use more than
+        // `16 * num_edges` bytes in total.
+        num_vertices.saturating_mul(num_vertices) <= 128 * num_edges
+    }
+
+    /// Add `v` to the B-list of `w`
+    pub(crate) fn insert(&mut self, w: usize, v: usize) {
+        match self {
+            Self::Sparse(b) => {
+                b[w].insert(v);
+            }
+            Self::Dense(b) => b[w].insert(v),
+        }
+    }
+
+    /// Empty the B-list of `v`
+    pub(crate) fn clear(&mut self, v: usize) {
+        match self {
+            Self::Sparse(b) => b[v].clear(),
+            Self::Dense(b) => b[v].clear(),
+        }
+    }
+
+    /// Empty the B-lists of the first `num_vertices` vertices
+    pub(crate) fn clear_all(&mut self, num_vertices: usize) {
+        match self {
+            Self::Sparse(b) => {
+                for b in &mut b[..num_vertices] {
+                    b.clear();
+                }
+            }
+            Self::Dense(b) => {
+                for b in &mut b[..num_vertices] {
+                    b.clear();
+                }
+            }
+        }
+    }
+
+    /// Empty the B-list of `v`, calling `f` for each removed entry
+    pub(crate) fn drain(&mut self, v: usize, mut f: impl FnMut(usize)) {
+        match self {
+            Self::Sparse(b) => b[v].drain().for_each(f),
+            Self::Dense(b) => {
+                b[v].ones().for_each(&mut f);
+                b[v].clear();
+            }
+        }
+    }
+}
@@ -0,0 +1,148 @@
+use alloc::{vec, vec::Vec};
+
+use petgraph::visit::{
+    EdgeRef, IntoEdgeReferences, IntoNeighbors, IntoNodeIdentifiers,
+    NodeIndexable,
+};
+
+/// Check whether a node lies on a cycle in an undirected graph
+///
+/// Self-loops are not considered and parallel edges are merged, so
+/// this is the case exactly if the node is adjacent to an edge that
+/// is not a bridge.
+pub(crate) fn is_on_undirected_cycle<G>(graph: G, node: G::NodeId) -> bool
+where
+    G: IntoNeighbors + IntoNodeIdentifiers + NodeIndexable,
+{
+    let mut adjacent = vec![Vec::new(); graph.node_bound()];
+    let mut num_edges = 0;
+    for n in graph.node_identifiers() {
+        let u = graph.to_index(n);
+        let mut neighbors: Vec<_> = graph
+            .neighbors(n)
+            .map(|n| graph.to_index(n))
+            .filter(|&v| v > u)
+            .collect();
+        neighbors.sort_unstable();
+        neighbors.dedup();
+        for v in neighbors {
+            adjacent[u].push((v, num_edges));
+            adjacent[v].push((u, num_edges));
+            num_edges += 1;
+        }
+    }
+    let is_bridge = bridges(&adjacent, num_edges);
+    adjacent
+        .get(graph.to_index(node))
+        .is_some_and(|adjacent| adjacent.iter().any(|&(_, e)| !is_bridge[e]))
+}
+
+/// Find all edges of an undirected graph that lie on a cycle
+///
+/// These are the edges that are not bridges. Self-loops and parallel
+/// edges always lie on a cycle.
+pub(crate) fn undirected_edges_on_cycles<G>(graph: G) -> Vec<G::EdgeId>
+where
+    G: IntoEdgeReferences + NodeIndexable,
+{
+    graph
+        .edge_references()
+        .zip(is_bridge(graph))
+        .filter(|(_, is_bridge)| !is_bridge)
+        .map(|(edge, _)| edge.id())
+        .collect()
+}
+
+/// Find all bridges of an undirected graph
+///
+/// These are the edges that do not lie on any cycle.
+pub(crate) fn undirected_bridges<G>(graph: G) -> Vec<G::EdgeId>
+where
+    G: IntoEdgeReferences + NodeIndexable,
+{
+    graph
+        .edge_references()
+        .zip(is_bridge(graph))
+        .filter(|(_, is_bridge)| *is_bridge)
+        .map(|(edge, _)| edge.id())
+        .collect()
+}
+
+// Whether each edge is a bridge, in the order of `edge_references`
+fn is_bridge<G>(graph: G) -> Vec<bool>
+where
+    G: IntoEdgeReferences + NodeIndexable,
+{
+    let mut adjacent = vec![Vec::new(); graph.node_bound()];
+    let mut num_edges = 0;
+    for (e, edge) in graph.edge_references().enumerate() {
+        let u = graph.to_index(edge.source());
+        let v = graph.to_index(edge.target());
+        if u != v {
+            adjacent[u].push((v, e));
+            adjacent[v].push((u, e));
+        }
+        num_edges += 1;
+    }
+    bridges(&adjacent, num_edges)
+}
+
+/// Find the bridges of an undirected multigraph
+///
+/// `adjacent[v]` lists the neighbours of vertex `v` together with the
+/// number of the connecting edge. Each edge has to be listed at both
+/// ends, apart from self-loops, which are not listed at all. Returns
+/// whether each edge is a bridge, that is, not part of any cycle.
+///
+/// Like in our version of Tarjan's algorithm, we use an explicit
+/// stack instead of recursion.
+fn bridges(adjacent: &[Vec<(usize, usize)>], num_edges: usize) -> Vec<bool> {
+    const UNVISITED: usize = usize::MAX;
+
+    let mut is_bridge = vec![false; num_edges];
+    // the order in which the vertices are visited
+    let mut order = vec![UNVISITED; adjacent.len()];
+    // the earliest vertex reachable without using the edge to the parent
+    let mut low = vec![UNVISITED; adjacent.len()];
+    let mut next = 0;
+    // vertex, edge to the parent, and position in the adjacency list
+    let mut frames: Vec<(usize, usize, usize)> = Vec::new();
+    for root in 0..adjacent.len() {
+        if order[root] != UNVISITED {
+            continue;
+        }
+        order[root] = next;
+        low[root] = next;
+        next += 1;
+        frames.push((root, UNVISITED, 0));
+        while let Some(frame) = frames.last_mut() {
+            let (v, parent_edge, pos) = *frame;
+            if let Some(&(w, e)) = adjacent[v].get(pos) {
+                frame.2 += 1;
+                if e == parent_edge {
+                    continue;
+                }
+                if order[w] == UNVISITED {
+                    // descend into `w`
+                    order[w] = next;
+                    low[w] = next;
+                    next += 1;
+                    frames.push((w, e, 0));
+                } else {
+                    low[v] = low[v].min(order[w]);
+                }
+                continue;
+            }
+
+            // all neighbours visited, return to the parent
+            frames.pop();
+            if let Some(&(parent, _, _)) = frames.last() {
+                low[parent] = low[parent].min(low[v]);
+                if low[v] > order[parent] {
+                    is_bridge[parent_edge] = true;
+                }
+            }
+        }
+    }
+    is_bridge
+}
@@ -0,0 +1,260 @@
+use alloc::string::{String, ToString};
+use alloc::{vec, vec::Vec};
+use core::fmt::{self, Display};
+
+use petgraph::visit::{EdgeRef, IntoEdges, NodeIndexable};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// A cycle in terms of its nodes
+///
+/// By default, each node is represented by its index as given by
+/// petgraph's
+/// [NodeIndexable::to_index](petgraph::visit::NodeIndexable::to_index),
+/// as returned by [cycles_raw](crate::Cycles::cycles_raw). With
+/// [cycles_typed](crate::Cycles::cycles_typed), the nodes are
+/// represented by their ids instead. With the `serde` feature, a cycle
+/// is (de)serialised as a sequence of nodes.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+pub struct Cycle<N = usize>(pub Vec<N>);
+
+impl<N> Cycle<N> {
+    /// The nodes of the cycle
+    pub fn nodes(&self) -> &[N] {
+        &self.0
+    }
+
+    /// The number of nodes in the cycle
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Whether the cycle has no nodes
+    ///
+    /// This is never the case for cycles found in a graph.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Whether the cycle is a self-loop, i.e. consists of a single node
+    pub fn is_self_loop(&self) -> bool {
+        self.0.len() == 1
+    }
+
+    /// Whether the cycle passes through `node`
+    pub fn contains(&self, node: &N) -> bool
+    where
+        N: PartialEq,
+    {
+        self.0.contains(node)
+    }
+
+    /// Rotate the cycle to start with its smallest node
+    ///
+    /// In contrast to [cycles_canonical](crate::Cycles::cycles_canonical),
+    /// the orientation of the cycle is never changed.
+    pub fn rotate_to_min(&mut self)
+    where
+        N: Ord,
+    {
+        canonicalise(&mut self.0, false)
+    }
+
+    /// The edges along the cycle in `graph`
+    ///
+    /// The `i`th edge connects the `i`th node to the next one,
+    /// wrapping around at the end. If there are parallel edges, the
+    /// first one in the order of petgraph's
+    /// [edges](petgraph::visit::IntoEdges::edges) is chosen.
+    ///
+    /// # Panics
+    ///
+    /// The iterator panics if two consecutive nodes are not connected
+    /// by an edge.
+    pub fn edges<'a, G>(
+        &'a self,
+        graph: G,
+    ) -> impl Iterator<Item = G::EdgeId> + 'a
+    where
+        G: IntoEdges<NodeId = N> + 'a,
+        N: Copy + PartialEq,
+    {
+        let next = self.0.iter().cycle().skip(1);
+        self.0.iter().zip(next).map(move |(&from, &to)| {
+            graph
+                .edges(from)
+                .find(|e| e.target() == to)
+                .expect("consecutive nodes in a cycle must be connected")
+                .id()
+        })
+    }
+}
+
+impl<N> From<Vec<N>> for Cycle<N> {
+    fn from(nodes: Vec<N>) -> Self {
+        Self(nodes)
+    }
+}
+
+impl<N> From<Cycle<N>> for Vec<N> {
+    fn from(cycle: Cycle<N>) -> Self {
+        cycle.0
+    }
+}
+
+impl<N: Display> Display for Cycle<N> {
+    /// Write the cycle in the same notation as [format_cycle]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, n) in self.0.iter().chain(self.0.first()).enumerate() {
+            if i > 0 {
+                f.write_str(" -> ")?;
+            }
+            write!(f, "{n}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Many cycles stored in a single buffer
+///
+/// Returned by [cycles_flat](crate::Cycles::cycles_flat). The nodes of
+/// all cycles are concatenated, and the cycle with number `i` consists
+/// of the nodes from position `offsets()[i]` up to `offsets()[i + 1]`.
+/// In contrast to a `Vec` for each cycle, this needs only two
+/// allocations in total.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct FlatCycles<N> {
+    nodes: Vec<N>,
+    offsets: Vec<usize>,
+}
+
+impl<N> FlatCycles<N> {
+    pub(crate) fn new() -> Self {
+        Self {
+            nodes: Vec::new(),
+            offsets: vec![0],
+        }
+    }
+
+    pub(crate) fn push(&mut self, cycle: &[N])
+    where
+        N: Clone,
+    {
+        self.nodes.extend_from_slice(cycle);
+        self.offsets.push(self.nodes.len());
+    }
+
+    /// The number of cycles
+    pub fn len(&self) -> usize {
+        self.offsets.len() - 1
+    }
+
+    /// Whether there are no cycles
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The nodes of the cycle with number `i`
+    ///
+    /// # Panics
+    ///
+    /// Panics if `i` is not smaller than [len](FlatCycles::len).
+    pub fn cycle(&self, i: usize) -> &[N] {
+        &self.nodes[self.offsets[i]..self.offsets[i + 1]]
+    }
+
+    /// Iterate over the nodes of all cycles
+    pub fn iter(&self) -> impl ExactSizeIterator<Item = &[N]> + '_ {
+        self.offsets
+            .windows(2)
+            .map(|range| &self.nodes[range[0]..range[1]])
+    }
+
+    /// The concatenated nodes of all cycles
+    pub fn nodes(&self) -> &[N] {
+        &self.nodes
+    }
+
+    /// The position of the first node of each cycle in
+    /// [nodes](FlatCycles::nodes), followed by the total number of
+    /// nodes
+    pub fn offsets(&self) -> &[usize] {
+        &self.offsets
+    }
+}
+
+/// Format a cycle in arrow notation
+///
+/// Each node is represented by its index as given by petgraph's
+/// [NodeIndexable::to_index]. The first node is repeated at the end,
+/// so that a cycle through the nodes with indices 0, 1, and 2 is
+/// formatted as `0 -> 1 -> 2 -> 0`.
+///
+/// # Example
+///
+/// ```rust
+/// use graph_cycles::{format_cycle, Cycles};
+/// use petgraph::graph::Graph;
+///
+/// let g = Graph::<(), ()>::from_edges([(0, 1), (1, 2), (2, 0)]);
+/// for cycle in g.cycles() {
+///     println!("{}", format_cycle(&g, &cycle));
+/// }
+/// ```
+pub fn format_cycle<G: NodeIndexable>(graph: G, cycle: &[G::NodeId]) -> String {
+    format_cycle_with(cycle, |n| graph.to_index(n).to_string())
+}
+
+/// Format a cycle in arrow notation with custom node labels
+///
+/// Like [format_cycle], but each node `n` is represented by `label(n)`.
+pub fn format_cycle_with<N, F>(cycle: &[N], mut label: F) -> String
+where
+    N: Copy,
+    F: FnMut(N) -> String,
+{
+    let mut formatted = String::new();
+    for (i, &n) in cycle.iter().chain(cycle.first()).enumerate() {
+        if i > 0 {
+            formatted.push_str(" -> ");
+        }
+        formatted.push_str(&label(n));
+    }
+    formatted
+}
+
+/// Remove cycles that are equal up to rotation and reversal
+///
+/// Each cycle is brought into a canonical form, starting with its
+/// smallest node and continuing towards the smaller of its two
+/// neighbours. The canonical forms are sorted and duplicates removed.
+/// This is useful to compare cycles of undirected graphs found in
+/// different orientations, for example in a directed copy with edges
+/// in both directions. Since a cycle and its reversal are merged,
+/// this is usually not what you want for directed graphs.
+pub fn merge_equivalent_cycles<N: Ord>(mut cycles: Vec<Vec<N>>) -> Vec<Vec<N>> {
+    for cycle in &mut cycles {
+        canonicalise(cycle, true);
+    }
+    cycles.sort_unstable();
+    cycles.dedup();
+    cycles
+}
+
+/// Bring a cycle into canonical form
+///
+/// The cycle is rotated to start with its smallest node. For
+/// undirected graphs, we additionally choose the orientation in which
+/// the second node is smaller than the last one.
+pub(crate) fn canonicalise<N: Ord>(cycle: &mut [N], undirected: bool) {
+    let Some(start) = (0..cycle.len()).min_by_key(|&i| &cycle[i]) else {
+        return;
+    };
+    cycle.rotate_left(start);
+    if undirected && cycle.len() > 2 && cycle[1] > cycle[cycle.len() - 1] {
+        cycle[1..].reverse();
+    }
+}
@@ -0,0 +1,64 @@
+use alloc::{format, string::String, vec, vec::Vec};
+
+use petgraph::visit::{
+    EdgeRef, GraphProp, IntoEdgeReferences, IntoNodeIdentifiers, NodeIndexable,
+};
+
+/// Write a graph in Graphviz DOT format, highlighting a cycle
+///
+/// Nodes are labelled by their index as given by petgraph's
+/// [NodeIndexable::to_index]. The edges along the `cycle`, including
+/// the one from the last node back to the first one, are coloured
+/// red. If there are parallel edges, the first one in the order of
+/// petgraph's `edge_references` is highlighted.
+///
+/// # Example
+///
+/// ```rust
+/// use graph_cycles::{cycle_to_dot, Cycles};
+/// use petgraph::graph::Graph;
+///
+/// let g = Graph::<(), ()>::from_edges([(0, 1), (1, 2), (2, 0), (2, 3)]);
+/// let cycle = &g.cycles()[0];
+/// let dot = cycle_to_dot(&g, cycle);
+/// assert!(dot.contains("0 -> 1 [ color = red ]"));
+/// assert!(dot.contains("2 -> 3\n"));
+/// ```
+pub fn cycle_to_dot<G>(graph: G, cycle: &[G::NodeId]) -> String
+where
+    G: IntoEdgeReferences + IntoNodeIdentifiers + NodeIndexable + GraphProp,
+{
+    let directed = graph.is_directed();
+    let pairs: Vec<_> = cycle
+        .iter()
+        .zip(cycle.iter().cycle().skip(1))
+        .map(|(&from, &to)| (graph.to_index(from), graph.to_index(to)))
+        .collect();
+    // each pair of adjacent nodes is matched to a distinct edge
+    let mut is_matched = vec![false; pairs.len()];
+
+    let (kind, arrow) = if directed {
+        ("digraph", "->")
+    } else {
+        ("graph", "--")
+    };
+    let mut dot = format!("{kind} {{\n");
+    for n in graph.node_identifiers() {
+        dot.push_str(&format!("    {}\n", graph.to_index(n)));
+    }
+    for e in graph.edge_references() {
+        let from = graph.to_index(e.source());
+        let to = graph.to_index(e.target());
+        let matched = pairs.iter().zip(&mut is_matched).find(|(&pair, m)| {
+            !**m && (pair == (from, to) || (!directed && pair == (to, from)))
+        });
+        dot.push_str(&format!("    {from} {arrow} {to}"));
+        if let Some((_, is_matched)) = matched {
+            *is_matched = true;
+            dot.push_str(" [ color = red ]");
+        }
+        dot.push('\n');
+    }
+    dot.push_str("}\n");
+    dot
+}
@@ -0,0 +1,143 @@
+use alloc::{vec, vec::Vec};
+
+use petgraph::visit::{
+    GraphBase, GraphProp, IntoNeighbors, IntoNodeIdentifiers, NodeIndexable,
+};
+
+use crate::{
+    finder::Finder, scc::cyclic_components, CycleSearchOptions, Cycles,
+    NeighborOrder,
+};
+
+/// Find cycles in the same graph repeatedly
+///
+/// The strongly connected components of the graph and the adjacency
+/// lists within each component are computed once when the enumerator
+/// is created. Each query then reuses them, together with the buffers
+/// needed for the search.
+///
+/// # Example
+///
+/// ```rust
+/// use graph_cycles::CycleEnumerator;
+/// use petgraph::graph::Graph;
+///
+/// let g = Graph::<(), ()>::from_edges([(0, 1), (1, 0), (1, 2), (2, 0)]);
+/// let mut cycles = CycleEnumerator::new(&g);
+/// assert_eq!(cycles.all().len(), 2);
+/// assert_eq!(cycles.with_max_len(2).len(), 1);
+/// assert_eq!(cycles.through(2.into()).len(), 1);
+/// ```
+pub struct CycleEnumerator<'a, G: Cycles + ?Sized> {
+    graph: &'a G,
+    finders: Vec<Finder<G::NodeId, ()>>,
+    // component and position within the component for each node,
+    // keyed by `NodeIndexable::to_index`
+    positions: Vec<Option<(usize, usize)>>,
+    // current order of the adjacency lists in all finders
+    neighbor_order: NeighborOrder,
+}
+
+impl<'a, G> CycleEnumerator<'a, G>
+where
+    G: GraphBase,
+    for<'b> &'b G: GraphBase<NodeId = G::NodeId>
+        + IntoNeighbors
+        + IntoNodeIdentifiers
+        + NodeIndexable
+        + GraphProp,
+{
+    /// Prepare the search for cycles in `graph`
+    pub fn new(graph: &'a G) -> Self {
+        let finders: Vec<_> = cyclic_components(graph)
+            .map(|component| Finder::new(graph, component))
+            .collect();
+        let mut positions = vec![None; graph.node_bound()];
+        for (i, finder) in finders.iter().enumerate() {
+            for (pos, &n) in finder.nodes().iter().enumerate() {
+                positions[graph.to_index(n)] = Some((i, pos));
+            }
+        }
+        Self {
+            graph,
+            finders,
+            positions,
+            neighbor_order: NeighborOrder::AsIs,
+        }
+    }
+
+    /// Find all cycles
+    ///
+    /// The result is the same as for [Cycles::cycles].
+    pub fn all(&mut self) -> Vec<Vec<G::NodeId>> {
+        self.with_options(&CycleSearchOptions::default())
+    }
+
+    /// Find all cycles with at most `len` nodes
+    pub fn with_max_len(&mut self, len: usize) -> Vec<Vec<G::NodeId>> {
+        self.with_options(&CycleSearchOptions::new().max_len(len))
+    }
+
+    /// Find all cycles matching the `options`
+    ///
+    /// The result is the same as for [Cycles::cycles_with].
+    pub fn with_options(
+        &mut self,
+        options: &CycleSearchOptions<G::NodeId>,
+    ) -> Vec<Vec<G::NodeId>> {
+        let mut cycles = Vec::new();
+        let (min_len, max_len) = options.len_bounds();
+        let through = match options.through {
+            Some(node) => match self.positions[self.graph.to_index(node)] {
+                Some(pos) => Some(pos),
+                None => return cycles,
+            },
+            None => None,
+        };
+        if options.neighbor_order != self.neighbor_order {
+            self.set_neighbor_order(options.neighbor_order);
+        }
+        for (idx, finder) in self.finders.iter_mut().enumerate() {
+            // a cycle cannot be longer than its component
+            if finder.nodes().len() < min_len {
+                continue;
+            }
+            if through.is_some_and(|(component, _)| component != idx) {
+                continue;
+            }
+            finder.reset();
+            finder.set_len_bounds(min_len, max_len);
+            if let Some((_, start)) = through {
+                finder.start_only_from(start);
+            }
+            while cycles.len() < options.limit {
+                let Some((cycle, _)) = finder.next_cycle() else {
+                    break;
+                };
+                cycles.push(cycle.to_vec());
+            }
+        }
+        cycles
+    }
+
+    // Reorder the adjacency lists of all finders
+    fn set_neighbor_order(&mut self, order: NeighborOrder) {
+        for finder in &mut self.finders {
+            if order == NeighborOrder::AsIs {
+                // sorting cannot restore the original order
+                let nodes = finder.nodes().to_vec();
+                finder.reset_for(self.graph, nodes);
+            } else {
+                finder.sort_adjacent(self.graph, order);
+            }
+        }
+        self.neighbor_order = order;
+    }
+
+    /// Find all cycles through `node`
+    ///
+    /// The result is the same as for [Cycles::cycles_through_node].
+    pub fn through(&mut self, node: G::NodeId) -> Vec<Vec<G::NodeId>> {
+        self.with_options(&CycleSearchOptions::new().through(node))
+    }
+}
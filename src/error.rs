@@ -0,0 +1,26 @@
+use core::fmt::{self, Display};
+
+/// Error signalling that a search was cancelled
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct Cancelled;
+
+impl Display for Cancelled {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "cycle search was cancelled")
+    }
+}
+
+impl core::error::Error for Cancelled {}
+
+/// Error signalling that a search exceeded its budget of node
+/// expansions
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct BudgetExceeded;
+
+impl Display for BudgetExceeded {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "cycle search exceeded its budget of node expansions")
+    }
+}
+
+impl core::error::Error for BudgetExceeded {}
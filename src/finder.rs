@@ -0,0 +1,768 @@
+use alloc::vec::Vec;
+use core::cmp::Reverse;
+use core::hash::BuildHasher;
+use core::ops::ControlFlow;
+
+use fixedbitset::FixedBitSet;
+use petgraph::visit::{
+    EdgeRef, GraphProp, IntoEdges, IntoNeighbors, IntoNeighborsDirected,
+    NodeIndexable,
+};
+use petgraph::Direction::{Incoming, Outgoing};
+
+use crate::blists::BLists;
+use crate::hash::HashMap;
+use crate::NeighborOrder;
+
+/// Search for the cycles within a single strongly connected component
+///
+/// This is the low-level search behind [Cycles](crate::Cycles), for
+/// integrating the cycle search into a custom traversal. Usually, the
+/// nodes passed to [new](CycleFinder::new) form a strongly connected
+/// component, for example as computed by [petgraph::algo::tarjan_scc].
+/// In general, the finder reports the cycles in the subgraph induced
+/// by the given nodes. Edges to nodes outside this set are ignored.
+///
+/// Internally, each node is identified by its position in
+/// [nodes](CycleFinder::nodes). Each cycle starts with whichever of
+/// its nodes comes first in this order. Like for the methods of
+/// [Cycles](crate::Cycles), each cycle is found only once and in
+/// undirected graphs only in one orientation.
+///
+/// The search is suspended after each cycle and resumed by the next
+/// call to [next_cycle](CycleFinder::next_cycle). Its state is only
+/// changed by the methods of the finder, so it is fine to interleave
+/// searches in several finders with other work.
+///
+/// The finder uses hash sets with the hasher `S` for bookkeeping. This
+/// does not affect which cycles are found or their order. To use a
+/// different hasher than the default one, create the finder with
+/// [default](CycleFinder::default) and call
+/// [reset_for](CycleFinder::reset_for).
+///
+/// # Example
+///
+/// ```rust
+/// use std::ops::ControlFlow;
+///
+/// use graph_cycles::CycleFinder;
+/// use petgraph::{algo::tarjan_scc, graph::Graph};
+///
+/// let g = Graph::<(), ()>::from_edges([(0, 1), (1, 0), (1, 2), (2, 0)]);
+/// let mut num_cycles = 0;
+/// for component in tarjan_scc(&g) {
+///     let mut finder = CycleFinder::new(&g, component);
+///     finder.visit(|cycle| {
+///         println!("Found new cycle with vertices {cycle:?}");
+///         num_cycles += 1;
+///         ControlFlow::<()>::Continue(())
+///     });
+/// }
+/// assert_eq!(num_cycles, 2);
+/// ```
+#[derive(Clone, Debug)]
+pub struct CycleFinder<N, S = ahash::RandomState>(Finder<N, (), S>);
+
+impl<N, S: Default> Default for CycleFinder<N, S> {
+    /// Create a finder without any nodes
+    ///
+    /// This finder does not find any cycles until it is reset with
+    /// [reset_for](CycleFinder::reset_for).
+    fn default() -> Self {
+        Self(Finder::default())
+    }
+}
+
+impl<N: Copy> CycleFinder<N> {
+    /// Prepare the search for cycles through the nodes in `scc`
+    ///
+    /// The nodes in `scc` must be distinct.
+    pub fn new<G>(graph: G, scc: Vec<N>) -> Self
+    where
+        G: IntoNeighbors<NodeId = N> + NodeIndexable + GraphProp,
+    {
+        Self(Finder::new(graph, scc))
+    }
+}
+
+impl<N: Copy, S: BuildHasher + Default> CycleFinder<N, S> {
+    /// Prepare a new search for cycles through the nodes in `scc`
+    ///
+    /// This is the same as creating a new finder, but reuses the
+    /// memory allocated for the previous search.
+    pub fn reset_for<G>(&mut self, graph: G, scc: Vec<N>)
+    where
+        G: IntoNeighbors<NodeId = N> + NodeIndexable + GraphProp,
+    {
+        self.0.reset_for(graph, scc)
+    }
+
+    /// The nodes in the order in which they were passed to the finder
+    pub fn nodes(&self) -> &[N] {
+        self.0.nodes()
+    }
+
+    /// Store the B-lists of Johnson's algorithm as bit sets
+    /// (`Some(true)`) or hash sets (`Some(false)`)
+    ///
+    /// By default (`None`), bit sets are chosen for dense components.
+    /// This is only meant for benchmarking the two representations.
+    /// The choice is kept when the finder is reset. Calling this
+    /// restarts the search.
+    #[doc(hidden)]
+    pub fn set_dense_blists(&mut self, dense: Option<bool>) {
+        self.0.set_dense_blists(dense)
+    }
+
+    /// Resume the search until the next cycle is found
+    ///
+    /// Returns the nodes of the cycle, or `None` if there are no more
+    /// cycles.
+    pub fn next_cycle(&mut self) -> Option<&[N]> {
+        self.0.next_cycle().map(|(nodes, _)| nodes)
+    }
+
+    /// Apply the `visitor` to each remaining cycle until we are told
+    /// to stop
+    ///
+    /// If the visitor returns `ControlFlow::Break(b)`, the search is
+    /// suspended and this function returns `ControlFlow::Break(b)`.
+    /// Calling it again resumes the search after the last cycle.
+    pub fn visit<F, B>(&mut self, mut visitor: F) -> ControlFlow<B>
+    where
+        F: FnMut(&[N]) -> ControlFlow<B>,
+    {
+        self.0.visit(&mut |nodes, _| visitor(nodes))
+    }
+}
+
+/// Implementation of [CycleFinder], generalised to cycles in terms of
+/// edges
+///
+/// Nodes are identified by their position in the component. Instead
+/// of recursing, `circuit` is implemented as an explicit state
+/// machine, so that the search can be suspended after each cycle and
+/// resumed later. This also means that long paths cannot overflow the
+/// stack.
+///
+/// Each adjacent vertex is stored together with an edge payload of
+/// type `E`. When searching for cycles in terms of edges this is the
+/// edge id, otherwise `()`.
+///
+/// In undirected graphs, we do not go back along the edge we came
+/// from and only report each cycle in one orientation.
+#[derive(Clone, Debug)]
+pub(crate) struct Finder<N, E, S = ahash::RandomState> {
+    scc: Vec<N>,
+    // position in `scc` for each node, keyed by `NodeIndexable::to_index`
+    index: HashMap<usize, usize, S>,
+    adjacent: Vec<Vec<(usize, E)>>,
+    // scratch space for removing parallel edges
+    last_seen_from: Vec<usize>,
+    undirected: bool,
+    // whether the first vertex can only be left along a single edge
+    single_first_edge: bool,
+    blocked: FixedBitSet,
+    b: BLists<S>,
+    // scratch space for the vertices still to be unblocked
+    unblocked: Vec<usize>,
+    stack: Vec<N>,
+    // edges between the nodes on the stack
+    edges: Vec<E>,
+    // whether the last edge closes a cycle found previously
+    closed: bool,
+    frames: Vec<Frame>,
+    // all vertices visited since starting from the current `s`
+    visited: Vec<usize>,
+    is_visited: Vec<bool>,
+    s: usize,
+    next_s: usize,
+    // only start circuits from the vertices before this one
+    end_s: usize,
+    // whether vertices before `s` are excluded from the search
+    exclude_before_s: bool,
+    // number of leading vertices blocked by `start_from`
+    excluded: usize,
+    min_len: usize,
+    max_len: usize,
+    // weights of the edges in `adjacent`, if the path weight is bounded
+    weights: Vec<Vec<f64>>,
+    max_weight: f64,
+    // B-list representation chosen by the user instead of by density
+    dense_blists: Option<bool>,
+}
+
+/// Result of resuming the search
+pub(crate) enum Step<'a, N, E> {
+    /// The nodes and edges of the next cycle
+    Cycle(&'a [N], &'a [E]),
+    /// The search was interrupted before finding the next cycle
+    Interrupted,
+    /// There are no more cycles
+    Finished,
+}
+
+impl<N, E, S: Default> Default for Finder<N, E, S> {
+    fn default() -> Self {
+        Self {
+            scc: Vec::new(),
+            index: HashMap::default(),
+            adjacent: Vec::new(),
+            last_seen_from: Vec::new(),
+            undirected: false,
+            single_first_edge: false,
+            blocked: FixedBitSet::new(),
+            b: BLists::Sparse(Vec::new()),
+            unblocked: Vec::new(),
+            stack: Vec::new(),
+            edges: Vec::new(),
+            closed: false,
+            frames: Vec::new(),
+            visited: Vec::new(),
+            is_visited: Vec::new(),
+            s: 0,
+            next_s: 0,
+            end_s: 0,
+            exclude_before_s: true,
+            excluded: 0,
+            min_len: 0,
+            max_len: usize::MAX,
+            weights: Vec::new(),
+            max_weight: f64::INFINITY,
+            dense_blists: None,
+        }
+    }
+}
+
+// State of one (suspended) invocation of `circuit`
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+struct Frame {
+    v: usize,
+    next: usize,
+    f: bool,
+    // total weight of the path to `v`
+    weight: f64,
+}
+
+impl<N: Copy> Finder<N, ()> {
+    pub(crate) fn new<G>(graph: G, scc: Vec<N>) -> Self
+    where
+        G: IntoNeighbors<NodeId = N> + NodeIndexable + GraphProp,
+    {
+        let mut finder = Self::default();
+        finder.reset_for(graph, scc);
+        finder
+    }
+}
+
+impl<N: Copy, S: BuildHasher + Default> Finder<N, (), S> {
+    /// Prepare the search in a new component
+    ///
+    /// In contrast to creating a new finder, this reuses the
+    /// allocated buffers.
+    pub(crate) fn reset_for<G>(&mut self, graph: G, scc: Vec<N>)
+    where
+        G: IntoNeighbors<NodeId = N> + NodeIndexable + GraphProp,
+    {
+        self.set_component(graph, scc);
+        for (v, &n) in self.scc.iter().enumerate() {
+            let adjacent = &mut self.adjacent[v];
+            for w in graph.neighbors(n) {
+                let Some(&w) = self.index.get(&graph.to_index(w)) else {
+                    continue;
+                };
+                // parallel edges don't lead to distinct cycles
+                if self.last_seen_from[w] != v {
+                    self.last_seen_from[w] = v;
+                    adjacent.push((w, ()));
+                }
+            }
+        }
+        self.init_search();
+    }
+
+    /// Prepare the search in a new component, ignoring the direction
+    /// of all edges
+    pub(crate) fn reset_for_undirected<G>(&mut self, graph: G, scc: Vec<N>)
+    where
+        G: IntoNeighborsDirected<NodeId = N> + NodeIndexable + GraphProp,
+    {
+        self.set_component(graph, scc);
+        self.undirected = true;
+        for (v, &n) in self.scc.iter().enumerate() {
+            let adjacent = &mut self.adjacent[v];
+            let neighbors = graph
+                .neighbors_directed(n, Outgoing)
+                .chain(graph.neighbors_directed(n, Incoming));
+            for w in neighbors {
+                let Some(&w) = self.index.get(&graph.to_index(w)) else {
+                    continue;
+                };
+                // edges in opposite directions are parallel
+                if self.last_seen_from[w] != v {
+                    self.last_seen_from[w] = v;
+                    adjacent.push((w, ()));
+                }
+            }
+        }
+        self.init_search();
+    }
+}
+
+impl<N: Copy, E: Copy + PartialEq> Finder<N, E> {
+    pub(crate) fn with_edges<G>(graph: G, scc: Vec<N>) -> Self
+    where
+        G: IntoEdges<NodeId = N, EdgeId = E> + NodeIndexable + GraphProp,
+    {
+        let mut finder = Self::default();
+        finder.set_component(graph, scc);
+        for (v, &n) in finder.scc.iter().enumerate() {
+            let adjacent = &mut finder.adjacent[v];
+            for e in graph.edges(n) {
+                let Some(&w) = finder.index.get(&graph.to_index(e.target()))
+                else {
+                    continue;
+                };
+                // an undirected self-loop may be listed twice
+                if finder.undirected
+                    && w == v
+                    && adjacent.contains(&(w, e.id()))
+                {
+                    continue;
+                }
+                adjacent.push((w, e.id()));
+            }
+        }
+        finder.init_search();
+        finder
+    }
+}
+
+impl<N, E, S> Finder<N, E, S>
+where
+    N: Copy,
+    E: Copy + PartialEq,
+    S: BuildHasher + Default,
+{
+    // Set the nodes of the component and clear the adjacency lists
+    fn set_component<G>(&mut self, graph: G, scc: Vec<N>)
+    where
+        G: NodeIndexable<NodeId = N> + GraphProp,
+    {
+        let num_vertices = scc.len();
+        self.index.clear();
+        // clearing is linear in the capacity, so don't keep the
+        // memory of a much larger component
+        self.index.shrink_to(num_vertices);
+        self.index.extend(
+            scc.iter()
+                .enumerate()
+                .map(|(pos, &n)| (graph.to_index(n), pos)),
+        );
+        // we keep excess adjacency lists to reuse their memory
+        if self.adjacent.len() < num_vertices {
+            self.adjacent.resize_with(num_vertices, Vec::new);
+        }
+        for adjacent in &mut self.adjacent[..num_vertices] {
+            adjacent.clear();
+        }
+        self.last_seen_from.clear();
+        self.last_seen_from.resize(num_vertices, usize::MAX);
+        self.undirected = !graph.is_directed();
+        self.scc = scc;
+    }
+
+    // Prepare the search after the adjacency lists have been set
+    fn init_search(&mut self) {
+        let num_vertices = self.scc.len();
+        let num_edges =
+            self.adjacent[..num_vertices].iter().map(Vec::len).sum();
+        self.blocked.grow(num_vertices);
+        match self.dense_blists {
+            Some(dense) => self.b.reset_as(num_vertices, dense),
+            None => self.b.reset(num_vertices, num_edges),
+        }
+        if self.is_visited.len() < num_vertices {
+            self.is_visited.resize(num_vertices, false);
+        }
+        self.single_first_edge = false;
+        // the B-lists are already empty
+        self.reset_search();
+    }
+
+    /// Store the B-lists as bit sets (`Some(true)`) or hash sets
+    /// (`Some(false)`) instead of choosing based on the density
+    ///
+    /// This must be called before the search starts.
+    pub(crate) fn set_dense_blists(&mut self, dense: Option<bool>) {
+        self.dense_blists = dense;
+        self.init_search();
+    }
+
+    /// Only report cycles with at least `min_len` and at most
+    /// `max_len` nodes
+    pub(crate) fn set_len_bounds(&mut self, min_len: usize, max_len: usize) {
+        self.min_len = min_len;
+        self.max_len = max_len;
+    }
+
+    /// Sort the adjacency lists in the given `order`
+    ///
+    /// This only changes the order in which the cycles are found. It
+    /// must be called before the search starts. For
+    /// [AsIs](NeighborOrder::AsIs), the current order is kept, which
+    /// is only the order of the graph if the lists were not sorted
+    /// before.
+    pub(crate) fn sort_adjacent<G>(&mut self, graph: G, order: NeighborOrder)
+    where
+        G: NodeIndexable<NodeId = N>,
+    {
+        let scc = &self.scc;
+        let adjacent = &mut self.adjacent[..scc.len()];
+        match order {
+            NeighborOrder::AsIs => {}
+            NeighborOrder::Ascending => {
+                for adjacent in adjacent {
+                    adjacent.sort_by_key(|&(w, _)| graph.to_index(scc[w]));
+                }
+            }
+            NeighborOrder::Descending => {
+                for adjacent in adjacent {
+                    adjacent
+                        .sort_by_key(|&(w, _)| Reverse(graph.to_index(scc[w])));
+                }
+            }
+        }
+    }
+
+    /// Don't extend paths with a total edge weight above `max_weight`
+    ///
+    /// The cycles themselves are not checked, only the paths leading to
+    /// their last edge. This is only correct if all weights are
+    /// nonnegative, so that a path cannot become lighter again.
+    pub(crate) fn set_max_weight<W>(&mut self, mut weight: W, max_weight: f64)
+    where
+        W: FnMut(E) -> f64,
+    {
+        let num_vertices = self.scc.len();
+        self.weights.clear();
+        self.weights
+            .extend(self.adjacent[..num_vertices].iter().map(|adjacent| {
+                adjacent.iter().map(|&(_, e)| weight(e)).collect()
+            }));
+        self.max_weight = max_weight;
+    }
+
+    /// Only start circuits from the first `num` vertices
+    ///
+    /// Since each cycle is found starting from its earliest vertex,
+    /// the cycles through the first vertex are exactly those found
+    /// for `num = 1`.
+    pub(crate) fn with_roots(mut self, num: usize) -> Self {
+        self.end_s = num.min(self.scc.len());
+        self
+    }
+
+    /// Only start a circuit from vertex `s`, excluding all vertices
+    /// before it
+    ///
+    /// This finds exactly the cycles that are found starting from `s`
+    /// in a full search, i.e. those for which `s` is the earliest
+    /// vertex. It must be called on a freshly reset finder.
+    pub(crate) fn start_from(&mut self, s: usize) {
+        self.blocked.insert_range(..s);
+        self.excluded = s;
+        self.next_s = s;
+        self.end_s = s + 1;
+    }
+
+    /// Only start a circuit from vertex `s`, after a previous search
+    /// started with [start_from](Self::start_from) or this function
+    ///
+    /// This is the same as a [reset](Self::reset) followed by
+    /// [start_from](Self::start_from), except that the search options
+    /// are kept. Only the vertices visited by the previous search and
+    /// the B-lists of their neighbours are reset, so the cost is
+    /// proportional to the work done in the previous search instead of
+    /// the size of the component.
+    pub(crate) fn restart_from(&mut self, s: usize) {
+        self.stack.clear();
+        self.edges.clear();
+        self.closed = false;
+        self.frames.clear();
+        // B-list entries are only added for neighbours of visited
+        // vertices
+        for v in self.visited.drain(..) {
+            self.is_visited[v] = false;
+            self.blocked.set(v, false);
+            self.b.clear(v);
+            for &(w, _) in &self.adjacent[v] {
+                self.b.clear(w);
+            }
+        }
+        // apart from the visited ones, only the excluded vertices are
+        // blocked
+        if s > self.excluded {
+            self.blocked.set_range(self.excluded..s, true);
+        } else {
+            self.blocked.set_range(s..self.excluded, false);
+        }
+        self.excluded = s;
+        self.s = s;
+        self.next_s = s;
+        self.end_s = s + 1;
+        self.exclude_before_s = true;
+    }
+
+    /// Only start a circuit from vertex `v`, without excluding any
+    /// other vertices
+    ///
+    /// This finds exactly the cycles through `v`.
+    pub(crate) fn start_only_from(&mut self, v: usize) {
+        self.next_s = v;
+        self.end_s = v + 1;
+        self.exclude_before_s = false;
+    }
+
+    /// Prepare a new search
+    ///
+    /// All buffers are kept. Apart from restrictions on the edges
+    /// leaving the first vertex, all search options are reset.
+    pub(crate) fn reset(&mut self) {
+        self.b.clear_all(self.scc.len());
+        self.reset_search();
+    }
+
+    // Reset everything apart from the B-lists
+    //
+    // Buffers sized for a larger component are only cleared up to the
+    // size of the current one, so that the cost does not depend on
+    // previous components.
+    fn reset_search(&mut self) {
+        let num_vertices = self.scc.len();
+        self.blocked.set_range(..num_vertices, false);
+        self.stack.clear();
+        self.edges.clear();
+        self.closed = false;
+        self.frames.clear();
+        self.visited.clear();
+        self.is_visited[..num_vertices].fill(false);
+        self.s = 0;
+        self.next_s = 0;
+        self.end_s = self.scc.len();
+        self.exclude_before_s = true;
+        self.excluded = 0;
+        self.min_len = 0;
+        self.max_len = usize::MAX;
+        self.weights.clear();
+        self.max_weight = f64::INFINITY;
+    }
+
+    /// The nodes in the component
+    pub(crate) fn nodes(&self) -> &[N] {
+        &self.scc
+    }
+
+    /// Only leave the first vertex along the edge `e`
+    pub(crate) fn with_first_edge(mut self, e: E) -> Self {
+        if let Some(adjacent) = self.adjacent.first_mut() {
+            adjacent.retain(|&(_, f)| f == e);
+        }
+        self.single_first_edge = true;
+        self
+    }
+
+    /// Only leave the first vertex towards the vertex `w`
+    pub(crate) fn with_first_target(mut self, w: usize) -> Self {
+        if let Some(adjacent) = self.adjacent.first_mut() {
+            adjacent.retain(|&(v, _)| v == w);
+        }
+        self.single_first_edge = true;
+        self
+    }
+
+    /// Apply the `visitor` to the nodes and edges of each cycle
+    pub(crate) fn visit<F, B>(&mut self, visitor: &mut F) -> ControlFlow<B>
+    where
+        F: FnMut(&[N], &[E]) -> ControlFlow<B>,
+    {
+        while let Some((nodes, edges)) = self.next_cycle() {
+            visitor(nodes, edges)?;
+        }
+        ControlFlow::Continue(())
+    }
+
+    /// Resume the search until the next cycle is found
+    ///
+    /// Returns the nodes and edges of the cycle, where the `i`th edge
+    /// connects the `i`th node to the next one, wrapping around at
+    /// the end.
+    pub(crate) fn next_cycle(&mut self) -> Option<(&[N], &[E])> {
+        match self.next_cycle_or_interrupt(&mut || false) {
+            Step::Cycle(nodes, edges) => Some((nodes, edges)),
+            Step::Interrupted | Step::Finished => None,
+        }
+    }
+
+    /// Resume the search until the next cycle is found or we are
+    /// interrupted
+    ///
+    /// `interrupt` is called before each new invocation of `circuit`.
+    /// If it returns `true`, the search is suspended and can be
+    /// resumed later.
+    pub(crate) fn next_cycle_or_interrupt<I>(
+        &mut self,
+        interrupt: &mut I,
+    ) -> Step<'_, N, E>
+    where
+        I: FnMut() -> bool,
+    {
+        if self.closed {
+            self.edges.pop();
+            self.closed = false;
+        }
+        loop {
+            let Some(frame) = self.frames.last_mut() else {
+                // cycle finding algorithm from Johnson's paper:
+                // start a new circuit from the next vertex
+                if self.next_s >= self.end_s {
+                    return Step::Finished;
+                }
+                if interrupt() {
+                    return Step::Interrupted;
+                }
+                let s = self.next_s;
+                self.next_s += 1;
+                self.s = s;
+                // Resetting only the vertices visited from the previous
+                // starting vertex avoids quadratic run time for long
+                // paths. All vertices before `s` remain blocked.
+                // Their B-lists may keep stale entries, but since they
+                // are never entered again, these B-lists are never
+                // drained. All other B-lists are empty, because only
+                // vertices before `s` can be left unvisited when their
+                // B-lists are filled.
+                for v in self.visited.drain(..) {
+                    self.is_visited[v] = false;
+                    self.blocked.set(v, self.exclude_before_s && v < s);
+                    self.b.clear(v);
+                }
+                self.enter(s);
+                continue;
+            };
+
+            // L1:
+            if let Some(&(w, e)) = self.adjacent[frame.v].get(frame.next) {
+                let len = self.stack.len();
+                let weight = frame.weight
+                    + self.weights.get(frame.v).map_or(0., |w| w[frame.next]);
+                let can_extend =
+                    len < self.max_len && weight <= self.max_weight;
+                if w != self.s && !self.blocked[w] && can_extend && interrupt()
+                {
+                    return Step::Interrupted;
+                }
+                frame.next += 1;
+                if w == self.s {
+                    frame.f = true;
+                    if (self.min_len..=self.max_len).contains(&len)
+                        && self.is_canonical(e)
+                    {
+                        self.edges.push(e);
+                        self.closed = true;
+                        return Step::Cycle(&self.stack, &self.edges);
+                    }
+                } else if !self.blocked[w] {
+                    if can_extend {
+                        self.edges.push(e);
+                        self.enter(w);
+                        self.frames.last_mut().unwrap().weight = weight;
+                    } else {
+                        // We don't know whether there is a path back to
+                        // `s` via `w`, so we have to treat `v` as if
+                        // there were one. Otherwise `v` would remain
+                        // blocked and we could miss cycles through `v`
+                        // on shorter paths.
+                        frame.f = true;
+                    }
+                }
+                continue;
+            }
+
+            // L2:
+            let frame = self.frames.pop().unwrap();
+            let v = frame.v;
+            if frame.f {
+                self.unblock(v)
+            } else {
+                for &(w, _) in &self.adjacent[v] {
+                    self.b.insert(w, v);
+                }
+            }
+            self.stack.pop(); // v
+            match self.frames.last_mut() {
+                Some(parent) => {
+                    parent.f |= frame.f;
+                    self.edges.pop();
+                }
+                None => self.blocked.insert(self.s),
+            }
+        }
+    }
+
+    // Whether the path on the stack closed by the edge `e` is a cycle
+    // that should be reported
+    //
+    // If the first edge is fixed, the reverse orientation cannot be
+    // found, so there is no need to choose.
+    fn is_canonical(&self, e: E) -> bool {
+        if !self.undirected {
+            return true;
+        }
+        match self.frames.len() {
+            1 => true,
+            2 => {
+                // the two edges have to be distinct and we choose the
+                // orientation starting with the earlier edge
+                let first = self.edges[0];
+                let pos =
+                    |e| self.adjacent[self.s].iter().position(|a| a.1 == e);
+                first != e && (self.single_first_edge || pos(first) < pos(e))
+            }
+            len => {
+                self.single_first_edge
+                    || self.frames[1].v < self.frames[len - 1].v
+            }
+        }
+    }
+
+    // begin a new invocation of `circuit`
+    fn enter(&mut self, v: usize) {
+        self.stack.push(self.scc[v]);
+        self.blocked.insert(v);
+        if !self.is_visited[v] {
+            self.is_visited[v] = true;
+            self.visited.push(v);
+        }
+        self.frames.push(Frame {
+            v,
+            ..Default::default()
+        });
+    }
+
+    fn unblock(&mut self, v: usize) {
+        let unblocked = &mut self.unblocked;
+        unblocked.clear();
+        unblocked.push(v);
+        while let Some(v) = unblocked.pop() {
+            self.blocked.set(v, false);
+            let blocked = &mut self.blocked;
+            self.b.drain(v, |w| {
+                if blocked[w] {
+                    blocked.set(w, false);
+                    unblocked.push(w);
+                }
+            });
+        }
+    }
+}
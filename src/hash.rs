@@ -0,0 +1,8 @@
+/// Hash map with the `ahash` hasher by default, also available
+/// without `std`
+pub(crate) type HashMap<K, V, S = ahash::RandomState> =
+    hashbrown::HashMap<K, V, S>;
+
+/// Hash set with the `ahash` hasher by default, also available
+/// without `std`
+pub(crate) type HashSet<K, S = ahash::RandomState> = hashbrown::HashSet<K, S>;
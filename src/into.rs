@@ -0,0 +1,46 @@
+use alloc::vec::Vec;
+
+use petgraph::{
+    graph::{Graph, IndexType},
+    EdgeType,
+};
+
+use crate::{scc::cyclic_components, CycleFinder};
+
+/// Trait for finding all cycles while consuming the graph
+///
+/// This is useful if the graph is no longer needed afterwards, but
+/// the node weights are, for example to look up node labels.
+pub trait IntoCycles {
+    /// The node weight of the underlying graph
+    type NodeWeight;
+
+    /// Find all cycles and return them together with the node weights
+    ///
+    /// Each cycle is a `Vec` with the indices of all its nodes. Each
+    /// index refers to the position of the node weight in the second
+    /// returned `Vec`.
+    fn into_cycles(self) -> (Vec<Vec<usize>>, Vec<Self::NodeWeight>);
+}
+
+impl<N, E, Ty, Ix> IntoCycles for Graph<N, E, Ty, Ix>
+where
+    Ty: EdgeType,
+    Ix: IndexType,
+{
+    type NodeWeight = N;
+
+    fn into_cycles(self) -> (Vec<Vec<usize>>, Vec<N>) {
+        let mut cycles = Vec::new();
+        let mut finder: CycleFinder<_> = CycleFinder::default();
+        for component in cyclic_components(&self) {
+            finder.reset_for(&self, component);
+            while let Some(cycle) = finder.next_cycle() {
+                cycles.push(cycle.iter().map(|n| n.index()).collect());
+            }
+        }
+        let (nodes, _) = self.into_nodes_edges();
+        let weights = nodes.into_iter().map(|node| node.weight).collect();
+        (cycles, weights)
+    }
+}
@@ -0,0 +1,164 @@
+use alloc::vec::IntoIter;
+use alloc::vec::Vec;
+
+use petgraph::data::DataMap;
+use petgraph::visit::{
+    GraphBase, GraphProp, IntoNeighbors, IntoNodeIdentifiers, NodeIndexable,
+};
+#[cfg(feature = "rayon")]
+use rayon::iter::{
+    plumbing::UnindexedConsumer, IntoParallelIterator, ParallelIterator,
+};
+
+use crate::{finder::Finder, Cycles};
+
+/// Iterator over the cycles in a graph
+///
+/// Created by [Cycles::cycles_iter]. Cycles are found lazily, one at
+/// a time.
+pub struct CyclesIter<'a, G: Cycles + ?Sized> {
+    graph: &'a G,
+    components: IntoIter<Vec<G::NodeId>>,
+    finder: Finder<G::NodeId, ()>,
+}
+
+impl<'a, G: Cycles + ?Sized> CyclesIter<'a, G> {
+    pub(crate) fn new(graph: &'a G, components: Vec<Vec<G::NodeId>>) -> Self {
+        Self {
+            graph,
+            components: components.into_iter(),
+            finder: Finder::default(),
+        }
+    }
+}
+
+impl<G> Iterator for CyclesIter<'_, G>
+where
+    G: GraphBase,
+    for<'a> &'a G: GraphBase<NodeId = G::NodeId>
+        + IntoNeighbors
+        + IntoNodeIdentifiers
+        + NodeIndexable
+        + GraphProp,
+{
+    type Item = Vec<G::NodeId>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some((cycle, _)) = self.finder.next_cycle() {
+                return Some(cycle.to_vec());
+            }
+            let component = self.components.next()?;
+            self.finder.reset_for(self.graph, component);
+        }
+    }
+}
+
+/// Iterator over the node weights of the cycles in a graph
+///
+/// Created by [Cycles::cycles_weights_iter]. Cycles are found lazily,
+/// one at a time. The weights are borrowed from the graph and not
+/// from the iterator, so items can be kept after the iterator has
+/// been dropped. The graph cannot be modified while the iterator or
+/// any of its items are alive.
+pub struct CyclesWeightIter<'a, G: Cycles + ?Sized> {
+    cycles: CyclesIter<'a, G>,
+}
+
+impl<'a, G: Cycles + ?Sized> CyclesWeightIter<'a, G> {
+    pub(crate) fn new(cycles: CyclesIter<'a, G>) -> Self {
+        Self { cycles }
+    }
+}
+
+impl<'a, G> Iterator for CyclesWeightIter<'a, G>
+where
+    G: DataMap,
+    for<'b> &'b G: GraphBase<NodeId = G::NodeId>
+        + IntoNeighbors
+        + IntoNodeIdentifiers
+        + NodeIndexable
+        + GraphProp,
+{
+    type Item = Vec<&'a G::NodeWeight>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let graph = self.cycles.graph;
+        let cycle = self.cycles.next()?;
+        Some(
+            cycle
+                .into_iter()
+                .map(|n| graph.node_weight(n).unwrap())
+                .collect(),
+        )
+    }
+}
+
+/// Parallel iterator over the cycles in a graph
+///
+/// Created by [Cycles::cycles_par_iter]. The searches starting from
+/// the individual nodes of each strongly connected component run in
+/// parallel. The order of the cycles is unspecified, but collecting
+/// into a `Vec` yields the same order as [Cycles::cycles].
+#[cfg(feature = "rayon")]
+pub struct ParCyclesIter<'a, G: Cycles + ?Sized> {
+    graph: &'a G,
+    components: Vec<Vec<G::NodeId>>,
+}
+
+#[cfg(feature = "rayon")]
+impl<'a, G: Cycles + ?Sized> ParCyclesIter<'a, G> {
+    pub(crate) fn new(graph: &'a G, components: Vec<Vec<G::NodeId>>) -> Self {
+        Self { graph, components }
+    }
+}
+
+#[cfg(feature = "rayon")]
+impl<G> ParallelIterator for ParCyclesIter<'_, G>
+where
+    G: GraphBase + Sync,
+    G::NodeId: Send + Sync,
+    for<'a> &'a G: GraphBase<NodeId = G::NodeId>
+        + IntoNeighbors
+        + IntoNodeIdentifiers
+        + NodeIndexable
+        + GraphProp,
+{
+    type Item = Vec<G::NodeId>;
+
+    fn drive_unindexed<C>(self, consumer: C) -> C::Result
+    where
+        C: UnindexedConsumer<Self::Item>,
+    {
+        let Self { graph, components } = self;
+        let roots: Vec<_> = components
+            .iter()
+            .enumerate()
+            .flat_map(|(c, component)| {
+                (0..component.len()).map(move |s| (c, s))
+            })
+            .collect();
+        roots
+            .into_par_iter()
+            .map_init(
+                || (usize::MAX, Finder::<_, _>::default()),
+                |(current, finder), (c, s)| {
+                    // only rebuild the adjacency lists for a new component
+                    if *current == c {
+                        finder.restart_from(s);
+                    } else {
+                        finder.reset_for(graph, components[c].clone());
+                        finder.start_from(s);
+                        *current = c;
+                    }
+                    let mut cycles = Vec::new();
+                    while let Some((cycle, _)) = finder.next_cycle() {
+                        cycles.push(cycle.to_vec());
+                    }
+                    cycles
+                },
+            )
+            .flatten_iter()
+            .drive_unindexed(consumer)
+    }
+}
@@ -24,9 +24,28 @@
 //! });
 //! ```
 //!
+//! # Features
+//!
+//! - `std` (enabled by default): Use the standard library. Without
+//!   this feature, the crate itself only depends on `core` and `alloc`.
+//!   Note that petgraph 0.6 still requires the standard library.
+//! - `rayon`: Search the strongly connected components of a graph in
+//!   parallel with `par_cycles`, or even the individual components with
+//!   `par_cycles_within_scc`. `cycles_par_iter` returns a parallel
+//!   iterator over all cycles. Implies `std`.
+//! - `serde`: Serialisation and deserialisation of cycles in terms of
+//!   node indices, as returned by `cycles_raw`.
+//! - `smallvec`: Return cycles as `SmallVec` with `cycles_small`.
+//! - `tokio`: Send cycles to a tokio channel as they are found with
+//!   `stream_cycles`. Implies `std`.
+//! - `testing`: Graph generators, proptest strategies, and brute-force
+//!   reference searches for tests and benchmarks in the `testing`
+//!   module. Implies `std`.
+//!
 //! # Caveats
 //!
-//! This crate is essentially untested.
+//! The cycle search is tested against a brute-force search on many
+//! small random graphs, but has seen little use beyond that.
 //!
 //! # References
 //!
@@ -34,20 +53,108 @@
 //! Finding all the elementary circuits of a directed graph,
 //! SIAM Journal on Computing, 1975.
 //!
-use std::ops::ControlFlow;
-
-use ahash::AHashSet;
-use petgraph::{
-    algo::tarjan_scc,
-    stable_graph::IndexType,
-    visit::{GraphBase, IntoNeighbors, IntoNodeIdentifiers, NodeIndexable},
-    EdgeType, Graph,
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+extern crate alloc;
+
+mod basis;
+mod blists;
+mod bridges;
+mod cycle;
+mod dot;
+mod enumerator;
+mod error;
+mod finder;
+mod hash;
+mod into;
+mod iter;
+mod negative;
+mod options;
+mod progress;
+mod scc;
+mod shortest;
+#[cfg(any(test, feature = "testing"))]
+pub mod testing;
+mod triangles;
+
+pub use cycle::{
+    format_cycle, format_cycle_with, merge_equivalent_cycles, Cycle, FlatCycles,
+};
+pub use dot::cycle_to_dot;
+pub use enumerator::CycleEnumerator;
+pub use error::{BudgetExceeded, Cancelled};
+pub use finder::CycleFinder;
+pub use into::IntoCycles;
+#[cfg(feature = "rayon")]
+pub use iter::ParCyclesIter;
+pub use iter::{CyclesIter, CyclesWeightIter};
+pub use options::{CycleSearchOptions, NeighborOrder};
+pub use progress::{CycleCount, CycleProgress, VisitOutcome};
+
+use alloc::collections::BTreeMap;
+use alloc::{vec, vec::Vec};
+use core::cell::RefCell;
+use core::ops::ControlFlow;
+use core::sync::atomic::{AtomicBool, Ordering};
+
+use finder::{Finder, Step};
+use petgraph::adj::List;
+use petgraph::data::DataMap;
+use petgraph::visit::{
+    Data, EdgeFiltered, EdgeRef, GraphBase, GraphProp, IntoEdgeReferences,
+    IntoEdges, IntoNeighbors, IntoNeighborsDirected, IntoNodeIdentifiers,
+    NodeFiltered, NodeIndexable, Reversed,
 };
+use scc::{component_indices, cyclic_components, is_cyclic, tarjan_scc};
+#[cfg(feature = "smallvec")]
+use smallvec::SmallVec;
 
 /// Trait for identifying cycles in a graph
+///
+/// This trait is implemented for every graph type `G` such that `&G`
+/// implements petgraph's [IntoNeighbors], [IntoNodeIdentifiers],
+/// [NodeIndexable], and [GraphProp] visitor traits. This includes
+/// [Graph](petgraph::graph::Graph) as well as the other graph types
+/// provided by petgraph, for example
+/// [StableGraph](petgraph::stable_graph::StableGraph),
+/// [MatrixGraph](petgraph::matrix_graph::MatrixGraph), and
+/// [Csr](petgraph::csr::Csr). Nodes removed
+/// from a `StableGraph` or `MatrixGraph` are never part of a cycle. For a
+/// [GraphMap](petgraph::graphmap::GraphMap) the cycles consist of the
+/// node weights, which serve as node identifiers.
+///
+/// Since petgraph implements its visitor traits for references, the
+/// trait is also implemented for references to graphs. This is
+/// convenient in generic code that only holds a reference:
+///
+/// ```rust
+/// use graph_cycles::Cycles;
+/// use petgraph::graph::Graph;
+///
+/// fn num_cycles<G: Cycles>(g: G) -> usize {
+///     g.cycles().len()
+/// }
+///
+/// let g = Graph::<(), ()>::from_edges([(0, 1), (1, 0), (1, 1)]);
+/// assert_eq!((&g).cycles(), g.cycles());
+/// assert_eq!(num_cycles(&g), 2);
+/// ```
+///
+/// A node with an edge to itself forms a cycle consisting of only
+/// this node.
+///
+/// In undirected graphs, each cycle is only reported in one
+/// orientation. Apart from self-loops, cycles in undirected graphs
+/// consist of at least three nodes. In particular, going back and
+/// forth along an edge does not form a cycle.
+///
+/// The cycles, their order, and the node each cycle starts with only
+/// depend on the graph, including the order of its nodes and edges.
+/// They are the same in every run, even though the internal hash sets
+/// are seeded randomly.
 pub trait Cycles {
-    //! The node identifier of the underlying graph
-    type NodeId;
+    /// The node identifier of the underlying graph
+    type NodeId: Copy + PartialEq;
 
     /// Apply the `visitor` to each cycle until we are told to stop
     ///
@@ -58,6 +165,122 @@ pub trait Cycles {
     /// further cycles and returns `Some(b)`. Otherwise the return
     /// value is `None`.
     fn visit_cycles<F, B>(&self, visitor: F) -> Option<B>
+    where
+        F: FnMut(&Self, &[Self::NodeId]) -> ControlFlow<B>,
+    {
+        self.visit_cycles_with(&CycleSearchOptions::default(), visitor)
+    }
+
+    /// Apply the `visitor` to each cycle until we are told to stop,
+    /// reporting how the search ended
+    ///
+    /// Apart from the return value, this is the same as
+    /// [visit_cycles](Cycles::visit_cycles). If the visitor returns
+    /// `ControlFlow::Break(b)`, the result is `VisitOutcome::Broke(b)`.
+    /// Otherwise, all cycles have been visited and the result is
+    /// `VisitOutcome::Completed`.
+    fn try_visit_cycles<F, B>(&self, visitor: F) -> VisitOutcome<B>
+    where
+        F: FnMut(&Self, &[Self::NodeId]) -> ControlFlow<B>,
+    {
+        match self.visit_cycles(visitor) {
+            Some(b) => VisitOutcome::Broke(b),
+            None => VisitOutcome::Completed,
+        }
+    }
+
+    /// Apply the `visitor` to each cycle matching the `options` until
+    /// we are told to stop
+    ///
+    /// Apart from only visiting the cycles selected by the `options`,
+    /// this is the same as [visit_cycles](Cycles::visit_cycles).
+    fn visit_cycles_with<F, B>(
+        &self,
+        options: &CycleSearchOptions<Self::NodeId>,
+        visitor: F,
+    ) -> Option<B>
+    where
+        F: FnMut(&Self, &[Self::NodeId]) -> ControlFlow<B>;
+
+    /// Apply the `visitor` to each cycle until we are told to stop
+    ///
+    /// In addition to the graph and the nodes of the cycle, the
+    /// visitor is passed the index of the strongly connected component
+    /// that contains the cycle. Components are numbered from zero in
+    /// the order of [petgraph::algo::tarjan_scc]. Apart from that,
+    /// this is the same as [visit_cycles](Cycles::visit_cycles).
+    fn visit_cycles_with_scc<F, B>(&self, visitor: F) -> Option<B>
+    where
+        F: FnMut(&Self, usize, &[Self::NodeId]) -> ControlFlow<B>;
+
+    /// Apply the `visitor` to each cycle until we are told to stop
+    ///
+    /// In addition to the graph and the nodes of the cycle, the
+    /// visitor is passed the root node from which the search found
+    /// the cycle. This is always the first node of the cycle and does
+    /// not change between searches in the same graph, so it can serve
+    /// as a consistent starting point. Apart from that, this is the
+    /// same as [visit_cycles](Cycles::visit_cycles).
+    fn visit_cycles_rooted<F, B>(&self, mut visitor: F) -> Option<B>
+    where
+        F: FnMut(&Self, Self::NodeId, &[Self::NodeId]) -> ControlFlow<B>,
+    {
+        self.visit_cycles(|g, cycle| visitor(g, cycle[0], cycle))
+    }
+
+    /// Apply the `visitor` to each cycle starting from one of the
+    /// `starts` until we are told to stop
+    ///
+    /// Note that this does *not* find all cycles through the `starts`.
+    /// Johnson's algorithm starts a search from each node in turn,
+    /// only considering cycles through nodes that come later, and
+    /// here we only run the searches from the `starts`. So the cycles
+    /// found are exactly those whose first node, which is also the
+    /// root passed by [visit_cycles_rooted](Cycles::visit_cycles_rooted),
+    /// is one of the `starts`. To find all cycles through a given node,
+    /// use [visit_cycles_through_node](Cycles::visit_cycles_through_node)
+    /// instead. Apart from that, this is the same as
+    /// [visit_cycles](Cycles::visit_cycles).
+    fn visit_cycles_from<F, B>(
+        &self,
+        starts: &[Self::NodeId],
+        visitor: F,
+    ) -> Option<B>
+    where
+        F: FnMut(&Self, &[Self::NodeId]) -> ControlFlow<B>;
+
+    /// Apply the `visitor` to each cycle until we are told to stop or
+    /// the search is cancelled
+    ///
+    /// Apart from cancellation, this is the same as
+    /// [visit_cycles](Cycles::visit_cycles). The `cancel` flag is
+    /// checked each time the search advances to a new node, so the
+    /// search stops promptly even if no cycles are found for a long
+    /// time. If the flag is set, the search is abandoned and this
+    /// function returns `Err(Cancelled)`.
+    fn visit_cycles_cancellable<F, B>(
+        &self,
+        cancel: &AtomicBool,
+        visitor: F,
+    ) -> Result<Option<B>, Cancelled>
+    where
+        F: FnMut(&Self, &[Self::NodeId]) -> ControlFlow<B>;
+
+    /// Apply the `visitor` to each cycle until we are told to stop or
+    /// the search becomes too expensive
+    ///
+    /// Each time the search advances to a new node counts as one node
+    /// expansion. If the search would need more than
+    /// `node_expansions_budget` expansions in total, it is abandoned
+    /// and this function returns `Err(BudgetExceeded)`. Apart from
+    /// that, this is the same as [visit_cycles](Cycles::visit_cycles).
+    /// The budget bounds the run time independently of the number of
+    /// cycles, which can grow exponentially with the graph size.
+    fn visit_cycles_bounded<F, B>(
+        &self,
+        node_expansions_budget: u64,
+        visitor: F,
+    ) -> Result<Option<B>, BudgetExceeded>
     where
         F: FnMut(&Self, &[Self::NodeId]) -> ControlFlow<B>;
 
@@ -76,174 +299,4599 @@ pub trait Cycles {
         });
     }
 
+    /// Find all cycles, skipping strongly connected components that
+    /// are too expensive to search
+    ///
+    /// Like for [visit_cycles_bounded](Cycles::visit_cycles_bounded),
+    /// each time the search advances to a new node counts as one node
+    /// expansion. If the search in a strongly connected component
+    /// would need more than `per_scc_budget` expansions, it is
+    /// abandoned and none of the cycles in this component are
+    /// returned. All other components are searched completely.
+    ///
+    /// Returns the cycles found together with the indices of the
+    /// skipped components. Components are numbered from zero in the
+    /// order of [petgraph::algo::tarjan_scc].
+    fn cycles_with_budget(
+        &self,
+        per_scc_budget: u64,
+    ) -> (Vec<Vec<Self::NodeId>>, Vec<usize>);
+
     /// Find all cycles
     ///
     /// Each element of the returned `Vec` is a `Vec` of all nodes in one cycle.
     fn cycles(&self) -> Vec<Vec<Self::NodeId>>;
-}
 
-impl<N, E, Ty: EdgeType, Ix: IndexType> Cycles for Graph<N, E, Ty, Ix> {
-    type NodeId = <Graph<N, E, Ty, Ix> as GraphBase>::NodeId;
+    /// Find all cycles, storing them in a single buffer
+    ///
+    /// Apart from the representation, the result is the same as for
+    /// [cycles](Cycles::cycles). Since there is no separate allocation
+    /// for each cycle, this is more efficient for many short cycles.
+    fn cycles_flat(&self) -> FlatCycles<Self::NodeId> {
+        let mut cycles = FlatCycles::new();
+        self.visit_all_cycles(|_, cycle| cycles.push(cycle));
+        cycles
+    }
+
+    /// Find all cycles, storing them in `out`
+    ///
+    /// Any previous content of `out` is replaced by the cycles returned
+    /// by [cycles](Cycles::cycles). The allocations of `out` and of
+    /// the `Vec`s it contains are reused as far as possible, which
+    /// helps when searching many similar graphs.
+    fn cycles_into(&self, out: &mut Vec<Vec<Self::NodeId>>) {
+        let mut num_cycles = 0;
+        self.visit_all_cycles(|_, cycle| {
+            match out.get_mut(num_cycles) {
+                Some(stored) => {
+                    stored.clear();
+                    stored.extend_from_slice(cycle);
+                }
+                None => out.push(cycle.to_vec()),
+            }
+            num_cycles += 1;
+        });
+        out.truncate(num_cycles);
+    }
+
+    /// Find all cycles, grouped by strongly connected component
+    ///
+    /// Each element of the returned `Vec` contains the nodes of one
+    /// strongly connected component together with all cycles in it.
+    /// Components are in the order of [petgraph::algo::tarjan_scc].
+    /// Components without any cycles, i.e. single nodes without a
+    /// self-loop, are omitted.
+    #[allow(clippy::type_complexity)]
+    fn strongly_connected_cycle_components(
+        &self,
+    ) -> Vec<(Vec<Self::NodeId>, Vec<Vec<Self::NodeId>>)>;
+
+    /// Find all strongly connected components that contain a cycle
+    ///
+    /// These are the components with more than one node, together with
+    /// single nodes with a self-loop. Components are in the order of
+    /// [petgraph::algo::tarjan_scc]. No cycles are enumerated, so the
+    /// run time is linear in the graph size. A directed graph is
+    /// acyclic exactly if there are no such components. In undirected
+    /// graphs, these are the connected components with more than one
+    /// node, which need not contain a cycle.
+    fn non_trivial_sccs(&self) -> Vec<Vec<Self::NodeId>>;
+
+    /// Find all cycles in closed form
+    ///
+    /// Apart from repeating the first node of each cycle at the end,
+    /// this is the same as [cycles](Cycles::cycles). For example, the
+    /// closed form of a self-loop at `v` is `[v, v]`.
+    fn cycles_closed(&self) -> Vec<Vec<Self::NodeId>> {
+        let mut cycles = Vec::new();
+        self.visit_all_cycles(|_, cycle| {
+            let mut closed = Vec::with_capacity(cycle.len() + 1);
+            closed.extend_from_slice(cycle);
+            closed.push(cycle[0]);
+            cycles.push(closed)
+        });
+        cycles
+    }
+
+    /// Find all cycles in terms of pairs of adjacent nodes
+    ///
+    /// Each element of the returned `Vec` lists the `(from, to)` pairs
+    /// of the edges along one cycle, including the edge from the last
+    /// node back to the first one. A self-loop at `v` yields
+    /// `[(v, v)]`.
+    fn cycles_as_edges_pairs(&self) -> Vec<Vec<(Self::NodeId, Self::NodeId)>> {
+        let mut cycles = Vec::new();
+        self.visit_all_cycles(|_, cycle| {
+            let next = cycle.iter().cycle().skip(1);
+            cycles.push(cycle.iter().copied().zip(next.copied()).collect())
+        });
+        cycles
+    }
 
-    fn visit_cycles<F, B>(&self, mut visitor: F) -> Option<B>
+    /// Apply the `visitor` to the node weights of each cycle until
+    /// we are told to stop
+    ///
+    /// The first argument passed to the visitor is a reference to the
+    /// graph and the second one a slice with the weights of all nodes
+    /// that form the cycle. Apart from that, this is the same as
+    /// [visit_cycles](Cycles::visit_cycles).
+    fn visit_cycles_weights<F, B>(&self, mut visitor: F) -> Option<B>
     where
-        F: FnMut(&Graph<N, E, Ty, Ix>, &[Self::NodeId]) -> ControlFlow<B>,
+        Self: DataMap + GraphBase<NodeId = <Self as Cycles>::NodeId>,
+        F: FnMut(&Self, &[&<Self as Data>::NodeWeight]) -> ControlFlow<B>,
     {
-        for component in tarjan_scc(self) {
-            let mut finder = CycleFinder::new(self, component);
-            if let ControlFlow::Break(b) = finder.visit(&mut visitor) {
-                return Some(b);
-            }
-        }
-        None
+        let mut weights = Vec::new();
+        self.visit_cycles(|g, cycle| {
+            weights.clear();
+            weights.extend(cycle.iter().map(|&n| self.node_weight(n).unwrap()));
+            visitor(g, &weights)
+        })
     }
 
-    fn cycles(&self) -> Vec<Vec<Self::NodeId>> {
+    /// Apply the `visitor` to the node weights of each cycle
+    ///
+    /// In contrast to
+    /// [visit_cycles_weights](Cycles::visit_cycles_weights), the
+    /// visitor is only passed a slice with the weights of all nodes
+    /// that form the cycle and the search cannot be stopped early.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::collections::BTreeSet;
+    ///
+    /// use graph_cycles::Cycles;
+    /// use petgraph::graph::Graph;
+    ///
+    /// let mut g = Graph::<&str, ()>::new();
+    /// let a = g.add_node("a");
+    /// let b = g.add_node("b");
+    /// let c = g.add_node("c");
+    /// g.extend_with_edges([(a, b), (b, a), (b, c), (c, a)]);
+    ///
+    /// let mut cycles = BTreeSet::new();
+    /// g.visit_all_cycles_weighted(|weights| {
+    ///     let mut labels: Vec<_> = weights.iter().map(|w| **w).collect();
+    ///     labels.sort();
+    ///     cycles.insert(labels.concat());
+    /// });
+    /// assert_eq!(cycles, BTreeSet::from(["ab".into(), "abc".into()]));
+    /// ```
+    fn visit_all_cycles_weighted<F>(&self, mut visitor: F)
+    where
+        Self: DataMap + GraphBase<NodeId = <Self as Cycles>::NodeId>,
+        F: FnMut(&[&<Self as Data>::NodeWeight]),
+    {
+        self.visit_cycles_weights(|_, weights| {
+            visitor(weights);
+            ControlFlow::<(), ()>::Continue(())
+        });
+    }
+
+    /// Find all cycles in terms of node weights
+    ///
+    /// Each element of the returned `Vec` is a `Vec` with references
+    /// to the weights of all nodes in one cycle.
+    fn cycles_weights(&self) -> Vec<Vec<&<Self as Data>::NodeWeight>>
+    where
+        Self: DataMap + GraphBase<NodeId = <Self as Cycles>::NodeId>,
+    {
         let mut cycles = Vec::new();
-        self.visit_all_cycles(|_, cycle| cycles.push(cycle.to_vec()));
+        self.visit_all_cycles(|_, cycle| {
+            cycles.push(
+                cycle
+                    .iter()
+                    .map(|&n| self.node_weight(n).unwrap())
+                    .collect(),
+            )
+        });
         cycles
     }
-}
 
-// // TODO: when trying to use this on a petgraph::graph::Graph rust
-// //       complains that `IntoNeighbors` and `IntoNodeIdentifiers` are
-// //       not satisfied
-// impl<Graph> Cycles for Graph
-// where
-//     Graph: IntoNodeIdentifiers + IntoNeighbors + NodeIndexable,
-// {
-//     type NodeId = Graph::NodeId;
-
-//     fn visit_cycles<F, B>(&self, mut visitor: F) -> Option<B>
-//     where F: FnMut(&Graph, &[Self::NodeId]) -> ControlFlow<B> {
-//         for component in tarjan_scc(self) {
-//             let mut finder = CycleFinder::new(self, component);
-//             if let ControlFlow::Break(b) = finder.visit(&mut visitor) {
-//                 return Some(b);
-//             }
-//         }
-//         None
-//     }
-
-//     fn cycles(&self) -> Vec<Vec<Self::NodeId>>  {
-//         let mut cycles = Vec::new();
-//         self.visit_cycles(|_, cycle| {
-//             cycles.push(cycle.to_vec());
-//             ControlFlow::<(), ()>::Continue(())
-//         });
-//         cycles
-//     }
-// }
-
-#[derive(Clone, Debug, Eq, PartialEq)]
-struct CycleFinder<G, N> {
-    graph: G,
-    scc: Vec<N>,
-    blocked: Vec<bool>,
-    b: Vec<AHashSet<usize>>,
-    stack: Vec<N>,
-    s: usize,
-}
+    /// Find all cycles in the reversed graph
+    ///
+    /// This is the same as finding the cycles in a copy of the graph
+    /// where the direction of each edge is flipped, without actually
+    /// creating such a copy. Each cycle in the reversed graph is a
+    /// cycle of the original graph traversed backwards.
+    fn reversed_cycles(&self) -> Vec<Vec<Self::NodeId>>
+    where
+        for<'a> &'a Self: IntoNeighborsDirected;
 
-impl<G> CycleFinder<G, G::NodeId>
-where
-    G: IntoNodeIdentifiers + IntoNeighbors + NodeIndexable,
-{
-    fn new(graph: G, scc: Vec<G::NodeId>) -> Self {
-        let num_vertices = scc.len();
-        Self {
-            graph,
-            scc,
-            blocked: vec![false; num_vertices],
-            b: vec![Default::default(); num_vertices],
-            stack: Default::default(),
-            s: Default::default(),
-        }
+    /// Find all chordless cycles
+    ///
+    /// A chord is an edge between two nodes of a cycle that are not
+    /// adjacent along the cycle, in either direction. Cycles with a
+    /// chord are discarded during the search without being stored.
+    fn chordless_cycles(&self) -> Vec<Vec<Self::NodeId>>;
+
+    /// Find all cycles, ignoring the direction of the edges
+    ///
+    /// This is the same as finding the cycles in an undirected copy of
+    /// the graph, without actually creating such a copy. Edges between
+    /// the same nodes are merged, regardless of their direction. So
+    /// apart from self-loops, all cycles have at least three nodes
+    /// and each cycle is only reported in one orientation.
+    fn undirected_cycles(&self) -> Vec<Vec<Self::NodeId>>
+    where
+        for<'a> &'a Self: IntoNeighborsDirected;
+
+    /// Find all cycles in canonical form
+    ///
+    /// Each cycle is rotated such that it starts with its smallest
+    /// node. In undirected graphs, each cycle is in addition oriented
+    /// such that the second node is smaller than the last one. Apart
+    /// from that, the result is the same as for
+    /// [cycles](Cycles::cycles). In particular, the same cycle always
+    /// has the same representation, independent of the order in
+    /// which the nodes and edges were added to the graph.
+    fn cycles_canonical(&self) -> Vec<Vec<Self::NodeId>>
+    where
+        Self::NodeId: Ord;
+
+    /// Find all cycles in canonical form, sorted by length
+    ///
+    /// Each cycle is in the canonical form described for
+    /// [cycles_canonical](Cycles::cycles_canonical). Shorter cycles
+    /// come first and cycles of the same length are sorted
+    /// lexicographically by their nodes. This means that the output
+    /// does not depend on the order in which the nodes and edges were
+    /// added to the graph.
+    fn cycles_sorted(&self) -> Vec<Vec<Self::NodeId>>
+    where
+        Self::NodeId: Ord,
+    {
+        let mut cycles = self.cycles_canonical();
+        cycles.sort_unstable_by(|a, b| {
+            a.len().cmp(&b.len()).then_with(|| a.cmp(b))
+        });
+        cycles
+    }
+
+    /// Find at most `limit` cycles
+    ///
+    /// The search stops as soon as `limit` cycles have been found.
+    /// The result consists of the first `limit` cycles returned by
+    /// [cycles](Cycles::cycles). Which cycles these are depends on
+    /// the order of the strongly connected components and of the
+    /// nodes within each component, and ultimately on the order of
+    /// the nodes in the graph.
+    fn cycles_limited(&self, limit: usize) -> Vec<Vec<Self::NodeId>> {
+        self.cycles_with(&CycleSearchOptions::new().limit(limit))
+    }
+
+    /// Find at most `limit` cycles with at most `max_len` nodes each
+    ///
+    /// Longer paths are pruned during the search, which stops as soon
+    /// as `limit` cycles have been found. Like for
+    /// [cycles_limited](Cycles::cycles_limited), the result consists
+    /// of the first matching cycles in the order of
+    /// [cycles](Cycles::cycles).
+    fn cycles_up_to(
+        &self,
+        limit: usize,
+        max_len: usize,
+    ) -> Vec<Vec<Self::NodeId>> {
+        self.cycles_with(
+            &CycleSearchOptions::new().limit(limit).max_len(max_len),
+        )
+    }
+
+    /// Find all cycles in terms of node indices
+    ///
+    /// Each node is converted to its index with petgraph's
+    /// [NodeIndexable::to_index]. Apart from that, the result is the
+    /// same as for [cycles](Cycles::cycles).
+    fn cycles_raw(&self) -> Vec<Cycle>;
+
+    /// Find all cycles as [Cycle]s
+    ///
+    /// Apart from the element type, this is the same as
+    /// [cycles](Cycles::cycles).
+    fn cycles_typed(&self) -> Vec<Cycle<Self::NodeId>> {
+        let mut cycles = Vec::new();
+        self.visit_all_cycles(|_, cycle| cycles.push(Cycle(cycle.to_vec())));
+        cycles
+    }
+
+    /// Apply the `visitor` to the node indices of each cycle until we
+    /// are told to stop
+    ///
+    /// Each node is converted to its index with petgraph's
+    /// [NodeIndexable::to_index]. Apart from that, this is the same as
+    /// [visit_cycles](Cycles::visit_cycles).
+    fn visit_cycles_indices<F, B>(&self, visitor: F) -> Option<B>
+    where
+        F: FnMut(&Self, &[usize]) -> ControlFlow<B>;
+
+    /// Find all cycles in terms of node indices
+    ///
+    /// Like [cycles_raw](Cycles::cycles_raw), but each cycle is a plain
+    /// `Vec` of indices. The result does not borrow from the graph, so
+    /// it can for example be sent to another thread.
+    fn cycles_indices(&self) -> Vec<Vec<usize>> {
+        let mut cycles = Vec::new();
+        self.visit_cycles_indices(|_, cycle| {
+            cycles.push(cycle.to_vec());
+            ControlFlow::<()>::Continue(())
+        });
+        cycles
     }
 
-    fn visit<F, B>(&mut self, visitor: &mut F) -> ControlFlow<B>
+    /// Find all cycles matching the `options`
+    ///
+    /// Each element of the returned `Vec` is a `Vec` of all nodes in one cycle.
+    fn cycles_with(
+        &self,
+        options: &CycleSearchOptions<Self::NodeId>,
+    ) -> Vec<Vec<Self::NodeId>> {
+        let mut cycles = Vec::new();
+        self.visit_cycles_with(options, |_, cycle| {
+            cycles.push(cycle.to_vec());
+            ControlFlow::<()>::Continue(())
+        });
+        cycles
+    }
+
+    /// Apply the `visitor` to each cycle through `node` until we are
+    /// told to stop
+    ///
+    /// Apart from only visiting the cycles containing `node`, this is
+    /// the same as [visit_cycles](Cycles::visit_cycles). Each cycle
+    /// passed to the visitor starts with `node`. Other cycles are
+    /// never enumerated, so this can be much faster than filtering
+    /// the output of [visit_cycles](Cycles::visit_cycles).
+    fn visit_cycles_through_node<F, B>(
+        &self,
+        node: Self::NodeId,
+        visitor: F,
+    ) -> Option<B>
     where
-        F: FnMut(G, &[G::NodeId]) -> ControlFlow<B>,
+        F: FnMut(&Self, &[Self::NodeId]) -> ControlFlow<B>,
     {
-        // cycle finding algorithm from
-        for s in 0..self.scc.len() {
-            self.s = s;
-            self.blocked[s..].fill(false);
-            for b in &mut self.b[s + 1..] {
-                b.clear();
-            }
-            if let ControlFlow::Break(b) = self.circuit(s, visitor) {
-                return ControlFlow::Break(b);
+        self.visit_cycles_with(
+            &CycleSearchOptions::new().through(node),
+            visitor,
+        )
+    }
+
+    /// Find all cycles through `node`
+    ///
+    /// Each element of the returned `Vec` is a `Vec` of all nodes in
+    /// one cycle, starting with `node`.
+    fn cycles_through_node(
+        &self,
+        node: Self::NodeId,
+    ) -> Vec<Vec<Self::NodeId>> {
+        let mut cycles = Vec::new();
+        self.visit_cycles_through_node(node, |_, cycle| {
+            cycles.push(cycle.to_vec());
+            ControlFlow::<()>::Continue(())
+        });
+        cycles
+    }
+
+    /// Find all cycles traversing an edge from `from` to `to`
+    ///
+    /// These are the cycles created by adding such an edge to a graph
+    /// without one, i.e. the simple paths from `to` back to `from`
+    /// closed by the new edge. The edge has to be part of the graph
+    /// already, otherwise there are no such cycles. Each element of
+    /// the returned `Vec` is a `Vec` of all nodes in one cycle,
+    /// starting with `from`, followed by `to`. Only the component
+    /// containing the edge is searched, so this is much cheaper than
+    /// finding all cycles after each insertion.
+    fn cycles_created_by_edge(
+        &self,
+        from: Self::NodeId,
+        to: Self::NodeId,
+    ) -> Vec<Vec<Self::NodeId>>;
+
+    /// Find all cycles consisting of three nodes
+    ///
+    /// Instead of a general cycle search, this intersects the
+    /// neighbours of adjacent nodes, which is much faster. In directed
+    /// graphs, each triangle `[a, b, c]` corresponds to the edges
+    /// `a -> b -> c -> a`. In undirected graphs, the nodes of each
+    /// triangle are ordered by their index as given by petgraph's
+    /// [NodeIndexable::to_index]. In both cases, the first node has
+    /// the smallest index, and each triangle is only reported once.
+    fn triangles(&self) -> Vec<[Self::NodeId; 3]>;
+
+    /// Find all cycles through all `required` nodes
+    ///
+    /// Each element of the returned `Vec` is a `Vec` of all nodes in
+    /// one cycle, starting with the first required node. Only the
+    /// cycles through the first required node are searched and
+    /// shorter cycles than the number of distinct required nodes are
+    /// skipped. If the required nodes are not all in the same
+    /// strongly connected component, there are no such cycles and the
+    /// search is skipped entirely. Without any required nodes, all
+    /// cycles are returned.
+    fn cycles_containing_all(
+        &self,
+        required: &[Self::NodeId],
+    ) -> Vec<Vec<Self::NodeId>>;
+
+    /// Find all cycles avoiding the `banned` nodes
+    ///
+    /// These are the cycles of the graph with the banned nodes
+    /// removed. Instead of filtering the output, the banned nodes are
+    /// excluded from the search from the start, so this is faster than
+    /// finding all cycles if the banned nodes are part of many of them.
+    fn cycles_avoiding(
+        &self,
+        banned: &[Self::NodeId],
+    ) -> Vec<Vec<Self::NodeId>>;
+
+    /// Count all cycles
+    ///
+    /// In contrast to `self.cycles().len()`, this does not store the
+    /// individual cycles. If there are more than `usize::MAX` cycles,
+    /// the returned count is `usize::MAX`.
+    fn count_cycles(&self) -> usize {
+        let mut count = 0usize;
+        self.visit_all_cycles(|_, _| count = count.saturating_add(1));
+        count
+    }
+
+    /// Count the cycles with exactly `len` nodes
+    ///
+    /// Paths longer than `len` are not explored. For `len = 3`, we
+    /// use a dedicated triangle search, which takes polynomial time.
+    fn count_cycles_of_length(&self, len: usize) -> u64;
+
+    /// Count the cycles, stopping once `cap` cycles have been found
+    ///
+    /// If there are fewer than `cap` cycles, this returns
+    /// `CycleCount::Exact` with their number. Otherwise, the search
+    /// is aborted and the result is `CycleCount::AtLeast(cap)`. This
+    /// bounds the run time even for graphs with exponentially many
+    /// cycles, at least for small `cap`.
+    fn cycle_count_capped(&self, cap: u64) -> CycleCount {
+        if cap == 0 {
+            return CycleCount::AtLeast(0);
+        }
+        let mut count = 0;
+        let res = self.visit_cycles(|_, _| {
+            count += 1;
+            if count < cap {
+                ControlFlow::Continue(())
+            } else {
+                ControlFlow::Break(())
             }
-            self.blocked[s] = true;
+        });
+        match res {
+            Some(()) => CycleCount::AtLeast(count),
+            None => CycleCount::Exact(count),
         }
-        ControlFlow::Continue(())
     }
 
-    fn circuit<B, F>(
-        &mut self,
-        v: usize,
-        visitor: &mut F,
-    ) -> ControlFlow<B, bool>
+    /// Count the cycles of each length
+    ///
+    /// The returned map assigns to each cycle length the number of
+    /// cycles with that many nodes. Lengths without any cycles are
+    /// omitted. The individual cycles are not stored, so apart from
+    /// the search itself only O(L) memory is needed, where L is the
+    /// length of the longest cycle. Note that the run time is still
+    /// exponential in the worst case.
+    fn cycle_length_histogram(&self) -> BTreeMap<usize, u64> {
+        let mut histogram = BTreeMap::new();
+        self.visit_all_cycles(|_, cycle| {
+            *histogram.entry(cycle.len()).or_default() += 1
+        });
+        histogram
+    }
+
+    /// Find all cycles, grouped by their length
+    ///
+    /// The returned map assigns to each cycle length all cycles with
+    /// that many nodes, in the order in which they are returned by
+    /// [cycles](Cycles::cycles). Lengths without any cycles are
+    /// omitted. Like for [cycles](Cycles::cycles), all cycles are
+    /// stored, which requires memory proportional to their total
+    /// length. Use [cycle_length_histogram](Cycles::cycle_length_histogram)
+    /// if only the numbers of cycles are needed.
+    fn cycles_grouped_by_length(
+        &self,
+    ) -> BTreeMap<usize, Vec<Vec<Self::NodeId>>> {
+        let mut groups = BTreeMap::new();
+        self.visit_all_cycles(|_, cycle| {
+            groups
+                .entry(cycle.len())
+                .or_insert_with(Vec::new)
+                .push(cycle.to_vec())
+        });
+        groups
+    }
+
+    /// Count the cycles through each node
+    ///
+    /// The returned vector is indexed by petgraph's
+    /// [NodeIndexable::to_index] and contains the number of cycles
+    /// passing through each node. The cycles are not stored, but they
+    /// all have to be enumerated, so the run time is exponential in
+    /// the worst case.
+    fn cycle_participation(&self) -> Vec<u64>;
+
+    /// Find all cycles and, for each node, the cycles it belongs to
+    ///
+    /// The first returned `Vec` contains the cycles as returned by
+    /// [cycles](Cycles::cycles). The second one is indexed by
+    /// petgraph's [NodeIndexable::to_index] and lists the positions in
+    /// the first `Vec` of all cycles through each node, in ascending
+    /// order.
+    #[allow(clippy::type_complexity)]
+    fn node_cycle_membership(
+        &self,
+    ) -> (Vec<Vec<Self::NodeId>>, Vec<Vec<usize>>);
+
+    /// Count the cycles of each length, reporting the progress
+    /// regularly
+    ///
+    /// After every `interval` cycles, `on_progress` is called with
+    /// the number of cycles found so far, both in total and for each
+    /// cycle length. It is called one final time after the search is
+    /// finished, with [CycleProgress::is_finished] returning `true`.
+    /// For `interval = 0` this is the only call. The final tally is
+    /// also returned.
+    ///
+    /// Like for [cycle_length_histogram](Cycles::cycle_length_histogram),
+    /// the cycles themselves are never stored.
+    fn visit_cycle_counts<F>(
+        &self,
+        interval: usize,
+        mut on_progress: F,
+    ) -> CycleProgress
     where
-        F: FnMut(G, &[G::NodeId]) -> ControlFlow<B>,
+        F: FnMut(&CycleProgress),
     {
-        let mut f = false;
-        self.stack.push(self.scc[v]);
-        self.blocked[v] = true;
-
-        // L1:
-        for w in self.adjacent_vertices(v) {
-            if w == self.s {
-                if let ControlFlow::Break(b) = visitor(self.graph, &self.stack)
-                {
-                    return ControlFlow::Break(b);
-                }
-                f = true;
-            } else if !self.blocked[w]
-                && matches!(
-                    self.circuit(w, visitor),
-                    ControlFlow::Continue(true)
-                )
-            {
-                f = true;
+        let mut progress = CycleProgress::default();
+        let mut until_report = interval;
+        self.visit_all_cycles(|_, cycle| {
+            progress.add(cycle.len());
+            if interval > 0 {
+                until_report -= 1;
+                if until_report == 0 {
+                    on_progress(&progress);
+                    until_report = interval;
+                }
             }
-        }
+        });
+        progress.finished = true;
+        on_progress(&progress);
+        progress
+    }
 
-        // L2:
-        if f {
-            self.unblock(v)
-        } else {
-            for w in self.adjacent_vertices(v) {
-                self.b[w].insert(v);
+    /// Check whether there is at least one cycle
+    ///
+    /// The search stops as soon as the first cycle is found.
+    fn has_cycle(&self) -> bool {
+        self.visit_cycles(|_, _| ControlFlow::Break(())).is_some()
+    }
+
+    /// Find a single cycle, if there is one
+    ///
+    /// The search stops as soon as the first cycle is found, so this
+    /// is as cheap as [has_cycle](Cycles::has_cycle). The returned
+    /// cycle is the first one returned by [cycles](Cycles::cycles).
+    fn first_cycle(&self) -> Option<Vec<Self::NodeId>> {
+        self.visit_cycles(|_, cycle| ControlFlow::Break(cycle.to_vec()))
+    }
+
+    /// Sort the nodes topologically or find a cycle preventing this
+    ///
+    /// If the graph is acyclic, returns `Ok` with all nodes ordered
+    /// such that each edge points from an earlier node to a later one.
+    /// Otherwise, returns `Err` with the nodes of the cycle that would
+    /// be returned by [first_cycle](Cycles::first_cycle). In contrast
+    /// to [petgraph::algo::toposort], the error contains a complete
+    /// cycle instead of a single node on it. An undirected graph is
+    /// acyclic if it is a forest, in which case the nodes are returned
+    /// in an unspecified order.
+    fn topo_sort_or_cycle(
+        &self,
+    ) -> Result<Vec<Self::NodeId>, Vec<Self::NodeId>>;
+
+    /// Check whether there is a cycle with at most `k` nodes
+    ///
+    /// Paths longer than `k` are not explored and the search stops as
+    /// soon as the first such cycle is found, so this is much cheaper
+    /// than enumerating all cycles if `k` is small.
+    fn has_cycle_of_length_at_most(&self, k: usize) -> bool {
+        let options = CycleSearchOptions::new().max_len(k);
+        self.visit_cycles_with(&options, |_, _| ControlFlow::Break(()))
+            .is_some()
+    }
+
+    /// Check whether a node lies on at least one cycle
+    ///
+    /// Instead of searching for cycles, this only checks whether the
+    /// node has a self-loop or, in directed graphs, whether its
+    /// strongly connected component has more than one node. In
+    /// undirected graphs, the node must be adjacent to an edge that
+    /// is not a bridge. The run time is linear in the graph size.
+    fn is_on_any_cycle(&self, node: Self::NodeId) -> bool;
+
+    /// Find the length of the shortest cycle
+    ///
+    /// Returns `None` if there are no cycles. In contrast to
+    /// enumerating all cycles, the run time is at most polynomial in
+    /// the graph size. A self-loop is a cycle of length one and two
+    /// nodes connected in both directions form a cycle of length two.
+    /// In undirected graphs, the shortest cycle apart from self-loops
+    /// has length three.
+    fn girth(&self) -> Option<usize>;
+
+    /// Find a shortest cycle
+    ///
+    /// Returns `None` if there are no cycles. Like
+    /// [girth](Cycles::girth), this never takes exponential time. If
+    /// there are several shortest cycles, the returned one goes
+    /// through the earliest possible node in the order of petgraph's
+    /// `node_identifiers`. Beyond that, ties are broken by the order
+    /// of each node's neighbours.
+    fn shortest_cycle(&self) -> Option<Vec<Self::NodeId>>;
+
+    /// Find a longest cycle
+    ///
+    /// Returns `None` if there are no cycles. If there are several
+    /// longest cycles, the one found first by
+    /// [visit_cycles](Cycles::visit_cycles) is returned. Since finding
+    /// a longest cycle is NP-hard, this visits all cycles, which can
+    /// take exponential time. Only the longest cycle found so far is
+    /// kept in memory.
+    fn longest_cycle(&self) -> Option<Vec<Self::NodeId>> {
+        let mut longest: Option<Vec<Self::NodeId>> = None;
+        self.visit_all_cycles(|_, cycle| {
+            if longest.as_ref().is_none_or(|l| cycle.len() > l.len()) {
+                longest = Some(cycle.to_vec());
             }
-        }
+        });
+        longest
+    }
+
+    /// Find a fundamental cycle basis
+    ///
+    /// This is meant for the analysis of undirected graphs: edge
+    /// directions are ignored and parallel edges are treated as a
+    /// single edge. We return one cycle for each edge outside a
+    /// spanning forest, that is, `|E| - |V| + C` cycles for a graph
+    /// with `|E|` edges, `|V|` nodes, and `C` connected components.
+    /// Every cycle of the graph can be obtained by combining basis
+    /// cycles. In contrast to enumerating all cycles, the run time is
+    /// polynomial in the graph size.
+    fn fundamental_cycle_basis(&self) -> Vec<Vec<Self::NodeId>>;
+
+    /// Find all cycles in parallel
+    ///
+    /// The strongly connected components of the graph are searched
+    /// in parallel. The cycles are returned in the same order as by
+    /// [cycles](Cycles::cycles).
+    #[cfg(feature = "rayon")]
+    fn par_cycles(&self) -> Vec<Vec<Self::NodeId>>
+    where
+        Self: Sync,
+        Self::NodeId: Send + Sync;
+
+    /// Find all cycles in parallel, even within a single strongly
+    /// connected component
+    ///
+    /// In contrast to [par_cycles](Cycles::par_cycles), the searches
+    /// starting from the individual nodes of each component run in
+    /// parallel. This helps for graphs with a few large components.
+    /// Each thread keeps its own search state, with memory roughly
+    /// proportional to the number of nodes and edges in the component
+    /// it is working on. The cycles are returned in the same order as
+    /// by [cycles](Cycles::cycles).
+    #[cfg(feature = "rayon")]
+    fn par_cycles_within_scc(&self) -> Vec<Vec<Self::NodeId>>
+    where
+        Self: Sync,
+        Self::NodeId: Send + Sync;
 
-        self.stack.pop(); // v
-        ControlFlow::Continue(f)
+    /// Iterate over all cycles in parallel
+    ///
+    /// This is the parallel iterator behind
+    /// [par_cycles_within_scc](Cycles::par_cycles_within_scc), for
+    /// processing the cycles in parallel as they are found. The work
+    /// is split by strongly connected component and by start node
+    /// within each component. The order in which cycles are produced
+    /// is unspecified.
+    #[cfg(feature = "rayon")]
+    fn cycles_par_iter(&self) -> ParCyclesIter<'_, Self>
+    where
+        Self: Sync,
+        Self::NodeId: Send + Sync;
+
+    /// Find all cycles, storing short cycles inline
+    ///
+    /// Apart from the element type, this is the same as
+    /// [cycles](Cycles::cycles). Cycles with at most eight nodes
+    /// don't require a separate heap allocation.
+    #[cfg(feature = "smallvec")]
+    fn cycles_small(&self) -> Vec<SmallVec<[Self::NodeId; 8]>> {
+        let mut cycles = Vec::new();
+        self.visit_all_cycles(|_, cycle| {
+            cycles.push(SmallVec::from_slice(cycle))
+        });
+        cycles
     }
 
-    fn unblock(&mut self, v: usize) {
-        self.blocked[v] = false;
-        let tmp = self.b[v].clone();
-        for w in tmp {
-            if self.blocked[w] {
-                self.unblock(w)
+    /// Send all cycles to a tokio channel
+    ///
+    /// Each cycle is sent as soon as it is found. While the channel is
+    /// full, the search is paused. If the receiver is closed, the
+    /// search is abandoned and this function returns `Err(Cancelled)`.
+    /// Since this blocks the current thread, it must not be called from
+    /// asynchronous code. Instead, the search can be run with
+    /// `tokio::task::spawn_blocking`, while the cycles are received
+    /// asynchronously.
+    #[cfg(feature = "tokio")]
+    fn stream_cycles(
+        &self,
+        tx: tokio::sync::mpsc::Sender<Vec<Self::NodeId>>,
+    ) -> Result<(), Cancelled> {
+        match self.visit_cycles(|_, cycle| {
+            match tx.blocking_send(cycle.to_vec()) {
+                Ok(()) => ControlFlow::Continue(()),
+                Err(_) => ControlFlow::Break(Cancelled),
             }
+        }) {
+            Some(cancelled) => Err(cancelled),
+            None => Ok(()),
         }
-        self.b[v].clear()
     }
 
-    fn adjacent_vertices(&self, v: usize) -> Vec<usize> {
-        self.graph
-            .neighbors(self.scc[v])
-            .filter_map(|n| self.scc.iter().position(|v| *v == n))
-            .collect()
+    /// Iterate over all cycles
+    ///
+    /// In contrast to [cycles](Cycles::cycles), the cycles are found
+    /// lazily. Each item is a `Vec` of all nodes in one cycle.
+    fn cycles_iter(&self) -> CyclesIter<'_, Self>;
+
+    /// Iterate over the node weights of all cycles
+    ///
+    /// Like for [cycles_iter](Cycles::cycles_iter), the cycles are
+    /// found lazily. Each item is a `Vec` with references to the
+    /// weights of all nodes in one cycle. These references borrow
+    /// from the graph, not from the iterator.
+    fn cycles_weights_iter(&self) -> CyclesWeightIter<'_, Self>
+    where
+        Self: DataMap + GraphBase<NodeId = <Self as Cycles>::NodeId>,
+    {
+        CyclesWeightIter::new(self.cycles_iter())
     }
 }
 
-#[cfg(test)]
-mod tests {
-    #[test]
-    fn test() {}
+impl<G> Cycles for G
+where
+    G: GraphBase,
+    for<'a> &'a G: GraphBase<NodeId = G::NodeId>
+        + IntoNeighbors
+        + IntoNodeIdentifiers
+        + NodeIndexable
+        + GraphProp,
+{
+    type NodeId = G::NodeId;
+
+    fn visit_cycles_with<F, B>(
+        &self,
+        options: &CycleSearchOptions<Self::NodeId>,
+        mut visitor: F,
+    ) -> Option<B>
+    where
+        F: FnMut(&G, &[Self::NodeId]) -> ControlFlow<B>,
+    {
+        let (min_len, max_len) = options.len_bounds();
+        let mut remaining = options.limit;
+        if remaining == 0 {
+            return None;
+        }
+        // all cycles through a node are within its component
+        let components = cyclic_components(self).filter(|component| {
+            options.through.is_none_or(|n| component.contains(&n))
+        });
+        let mut finder: Finder<_, _> = Finder::default();
+        for component in components {
+            // a cycle cannot be longer than its component
+            if component.len() < min_len {
+                continue;
+            }
+            let start = options
+                .through
+                .map(|node| component.iter().position(|&n| n == node).unwrap());
+            finder.reset_for(self, component);
+            finder.set_len_bounds(min_len, max_len);
+            finder.sort_adjacent(self, options.neighbor_order);
+            if let Some(start) = start {
+                finder.start_only_from(start);
+            }
+            while let Some((cycle, _)) = finder.next_cycle() {
+                if let ControlFlow::Break(b) = visitor(self, cycle) {
+                    return Some(b);
+                }
+                remaining -= 1;
+                if remaining == 0 {
+                    return None;
+                }
+            }
+        }
+        None
+    }
+
+    fn visit_cycles_with_scc<F, B>(&self, mut visitor: F) -> Option<B>
+    where
+        F: FnMut(&G, usize, &[Self::NodeId]) -> ControlFlow<B>,
+    {
+        let mut finder: Finder<_, _> = Finder::default();
+        let components = tarjan_scc(self).into_iter().enumerate();
+        for (idx, component) in
+            components.filter(|(_, component)| is_cyclic(self, component))
+        {
+            finder.reset_for(self, component);
+            if let ControlFlow::Break(b) =
+                finder.visit(&mut |cycle, _| visitor(self, idx, cycle))
+            {
+                return Some(b);
+            }
+        }
+        None
+    }
+
+    fn visit_cycles_from<F, B>(
+        &self,
+        starts: &[Self::NodeId],
+        mut visitor: F,
+    ) -> Option<B>
+    where
+        F: FnMut(&G, &[Self::NodeId]) -> ControlFlow<B>,
+    {
+        let mut is_start = vec![false; self.node_bound()];
+        for &n in starts {
+            is_start[self.to_index(n)] = true;
+        }
+        let mut finder: Finder<_, _> = Finder::default();
+        for component in cyclic_components(self) {
+            let roots: Vec<_> = component
+                .iter()
+                .enumerate()
+                .filter(|(_, &n)| is_start[self.to_index(n)])
+                .map(|(pos, _)| pos)
+                .collect();
+            if roots.is_empty() {
+                continue;
+            }
+            finder.reset_for(self, component);
+            for (i, s) in roots.into_iter().enumerate() {
+                if i == 0 {
+                    finder.start_from(s);
+                } else {
+                    finder.restart_from(s);
+                }
+                if let ControlFlow::Break(b) =
+                    finder.visit(&mut |cycle, _| visitor(self, cycle))
+                {
+                    return Some(b);
+                }
+            }
+        }
+        None
+    }
+
+    fn visit_cycles_cancellable<F, B>(
+        &self,
+        cancel: &AtomicBool,
+        mut visitor: F,
+    ) -> Result<Option<B>, Cancelled>
+    where
+        F: FnMut(&G, &[Self::NodeId]) -> ControlFlow<B>,
+    {
+        let mut is_cancelled = || cancel.load(Ordering::Relaxed);
+        let mut finder: Finder<_, _> = Finder::default();
+        for component in cyclic_components(self) {
+            finder.reset_for(self, component);
+            loop {
+                match finder.next_cycle_or_interrupt(&mut is_cancelled) {
+                    Step::Cycle(cycle, _) => {
+                        if let ControlFlow::Break(b) = visitor(self, cycle) {
+                            return Ok(Some(b));
+                        }
+                    }
+                    Step::Interrupted => return Err(Cancelled),
+                    Step::Finished => break,
+                }
+            }
+        }
+        Ok(None)
+    }
+
+    fn visit_cycles_bounded<F, B>(
+        &self,
+        node_expansions_budget: u64,
+        mut visitor: F,
+    ) -> Result<Option<B>, BudgetExceeded>
+    where
+        F: FnMut(&G, &[Self::NodeId]) -> ControlFlow<B>,
+    {
+        let mut remaining = node_expansions_budget;
+        let mut is_exhausted = || {
+            if remaining == 0 {
+                return true;
+            }
+            remaining -= 1;
+            false
+        };
+        let mut finder: Finder<_, _> = Finder::default();
+        for component in cyclic_components(self) {
+            finder.reset_for(self, component);
+            loop {
+                match finder.next_cycle_or_interrupt(&mut is_exhausted) {
+                    Step::Cycle(cycle, _) => {
+                        if let ControlFlow::Break(b) = visitor(self, cycle) {
+                            return Ok(Some(b));
+                        }
+                    }
+                    Step::Interrupted => return Err(BudgetExceeded),
+                    Step::Finished => break,
+                }
+            }
+        }
+        Ok(None)
+    }
+
+    fn cycles_with_budget(
+        &self,
+        per_scc_budget: u64,
+    ) -> (Vec<Vec<Self::NodeId>>, Vec<usize>) {
+        let mut cycles = Vec::new();
+        let mut skipped = Vec::new();
+        let mut component_cycles = Vec::new();
+        let mut finder: Finder<_, _> = Finder::default();
+        let components = tarjan_scc(self).into_iter().enumerate();
+        for (idx, component) in
+            components.filter(|(_, component)| is_cyclic(self, component))
+        {
+            let mut remaining = per_scc_budget;
+            let mut is_exhausted = || {
+                if remaining == 0 {
+                    return true;
+                }
+                remaining -= 1;
+                false
+            };
+            finder.reset_for(self, component);
+            component_cycles.clear();
+            loop {
+                match finder.next_cycle_or_interrupt(&mut is_exhausted) {
+                    Step::Cycle(cycle, _) => {
+                        component_cycles.push(cycle.to_vec())
+                    }
+                    Step::Interrupted => {
+                        skipped.push(idx);
+                        component_cycles.clear();
+                        break;
+                    }
+                    Step::Finished => break,
+                }
+            }
+            cycles.append(&mut component_cycles);
+        }
+        (cycles, skipped)
+    }
+
+    fn cycles(&self) -> Vec<Vec<Self::NodeId>> {
+        let mut cycles = Vec::new();
+        self.visit_all_cycles(|_, cycle| cycles.push(cycle.to_vec()));
+        cycles
+    }
+
+    fn non_trivial_sccs(&self) -> Vec<Vec<Self::NodeId>> {
+        cyclic_components(self).collect()
+    }
+
+    fn strongly_connected_cycle_components(
+        &self,
+    ) -> Vec<(Vec<Self::NodeId>, Vec<Vec<Self::NodeId>>)> {
+        let mut components = Vec::new();
+        let mut finder: Finder<_, _> = Finder::default();
+        for component in cyclic_components(self) {
+            finder.reset_for(self, component);
+            let mut cycles = Vec::new();
+            while let Some((cycle, _)) = finder.next_cycle() {
+                cycles.push(cycle.to_vec());
+            }
+            if !cycles.is_empty() {
+                components.push((finder.nodes().to_vec(), cycles));
+            }
+        }
+        components
+    }
+
+    #[cfg(feature = "rayon")]
+    fn par_cycles(&self) -> Vec<Vec<Self::NodeId>>
+    where
+        Self: Sync,
+        Self::NodeId: Send + Sync,
+    {
+        use rayon::prelude::*;
+
+        tarjan_scc(self)
+            .into_par_iter()
+            .filter(|component| is_cyclic(self, component))
+            .flat_map_iter(|component| {
+                let mut finder = Finder::new(self, component);
+                let mut cycles = Vec::new();
+                while let Some((cycle, _)) = finder.next_cycle() {
+                    cycles.push(cycle.to_vec());
+                }
+                cycles
+            })
+            .collect()
+    }
+
+    #[cfg(feature = "rayon")]
+    fn par_cycles_within_scc(&self) -> Vec<Vec<Self::NodeId>>
+    where
+        Self: Sync,
+        Self::NodeId: Send + Sync,
+    {
+        use rayon::prelude::*;
+
+        self.cycles_par_iter().collect()
+    }
+
+    #[cfg(feature = "rayon")]
+    fn cycles_par_iter(&self) -> ParCyclesIter<'_, Self>
+    where
+        Self: Sync,
+        Self::NodeId: Send + Sync,
+    {
+        ParCyclesIter::new(self, cyclic_components(self).collect())
+    }
+
+    fn topo_sort_or_cycle(
+        &self,
+    ) -> Result<Vec<Self::NodeId>, Vec<Self::NodeId>> {
+        let components = tarjan_scc(self);
+        // in undirected graphs, components with more than one node
+        // need not contain a cycle
+        let mut finder: Finder<_, _> = Finder::default();
+        let cycle = components.iter().find_map(|component| {
+            if !is_cyclic(self, component) {
+                return None;
+            }
+            finder.reset_for(self, component.clone());
+            finder.next_cycle().map(|(cycle, _)| cycle.to_vec())
+        });
+        if let Some(cycle) = cycle {
+            return Err(cycle);
+        }
+        // the components are returned in reverse topological order
+        Ok(components.into_iter().rev().flatten().collect())
+    }
+
+    fn triangles(&self) -> Vec<[Self::NodeId; 3]> {
+        let mut triangles = Vec::new();
+        triangles::visit_triangles(self, |triangle| triangles.push(triangle));
+        triangles
+    }
+
+    fn cycles_created_by_edge(
+        &self,
+        from: Self::NodeId,
+        to: Self::NodeId,
+    ) -> Vec<Vec<Self::NodeId>> {
+        let mut cycles = Vec::new();
+        let Some(mut component) = tarjan_scc(self)
+            .into_iter()
+            .find(|component| component.contains(&from))
+        else {
+            return cycles;
+        };
+        let Some(target) = component.iter().position(|&n| n == to) else {
+            return cycles;
+        };
+        let source = component.iter().position(|&n| n == from).unwrap();
+        component.swap(0, source);
+        let target = match target {
+            0 => source,
+            t if t == source => 0,
+            t => t,
+        };
+        let mut finder = Finder::new(self, component)
+            .with_roots(1)
+            .with_first_target(target);
+        while let Some((cycle, _)) = finder.next_cycle() {
+            cycles.push(cycle.to_vec());
+        }
+        cycles
+    }
+
+    fn cycles_containing_all(
+        &self,
+        required: &[Self::NodeId],
+    ) -> Vec<Vec<Self::NodeId>> {
+        let Some((&first, rest)) = required.split_first() else {
+            return self.cycles();
+        };
+        let mut cycles = Vec::new();
+        let Some(component) = tarjan_scc(self)
+            .into_iter()
+            .find(|component| component.contains(&first))
+        else {
+            return cycles;
+        };
+        if !rest.iter().all(|n| component.contains(n)) {
+            return cycles;
+        }
+        let start = component.iter().position(|&n| n == first).unwrap();
+        let num_distinct = required
+            .iter()
+            .enumerate()
+            .filter(|&(i, n)| !required[..i].contains(n))
+            .count();
+        let mut finder = Finder::new(self, component);
+        finder.set_len_bounds(num_distinct, usize::MAX);
+        finder.start_only_from(start);
+        while let Some((cycle, _)) = finder.next_cycle() {
+            if rest.iter().all(|n| cycle.contains(n)) {
+                cycles.push(cycle.to_vec());
+            }
+        }
+        cycles
+    }
+
+    fn cycles_avoiding(
+        &self,
+        banned: &[Self::NodeId],
+    ) -> Vec<Vec<Self::NodeId>> {
+        let mut is_banned = vec![false; self.node_bound()];
+        for &n in banned {
+            is_banned[self.to_index(n)] = true;
+        }
+        let filtered =
+            NodeFiltered::from_fn(self, |n| !is_banned[self.to_index(n)]);
+        let mut cycles = Vec::new();
+        let mut finder: Finder<_, _> = Finder::default();
+        for component in cyclic_components(&filtered) {
+            finder.reset_for(&filtered, component);
+            while let Some((cycle, _)) = finder.next_cycle() {
+                cycles.push(cycle.to_vec());
+            }
+        }
+        cycles
+    }
+
+    fn count_cycles_of_length(&self, len: usize) -> u64 {
+        let mut count = 0;
+        if len == 3 {
+            triangles::visit_triangles(self, |_| count += 1);
+        } else {
+            let options = CycleSearchOptions::new().min_len(len).max_len(len);
+            self.visit_cycles_with(&options, |_, _| {
+                count += 1;
+                ControlFlow::<()>::Continue(())
+            });
+        }
+        count
+    }
+
+    fn node_cycle_membership(
+        &self,
+    ) -> (Vec<Vec<Self::NodeId>>, Vec<Vec<usize>>) {
+        let mut cycles = Vec::new();
+        let mut membership = vec![Vec::new(); self.node_bound()];
+        self.visit_all_cycles(|g, cycle| {
+            for &n in cycle {
+                membership[g.to_index(n)].push(cycles.len());
+            }
+            cycles.push(cycle.to_vec());
+        });
+        (cycles, membership)
+    }
+
+    fn cycle_participation(&self) -> Vec<u64> {
+        let mut count = vec![0; self.node_bound()];
+        self.visit_all_cycles(|g, cycle| {
+            for &n in cycle {
+                count[g.to_index(n)] += 1;
+            }
+        });
+        count
+    }
+
+    fn reversed_cycles(&self) -> Vec<Vec<Self::NodeId>>
+    where
+        for<'a> &'a Self: IntoNeighborsDirected,
+    {
+        let reversed = Reversed(self);
+        let mut cycles = Vec::new();
+        let mut finder: Finder<_, _> = Finder::default();
+        for component in cyclic_components(reversed) {
+            finder.reset_for(reversed, component);
+            while let Some((cycle, _)) = finder.next_cycle() {
+                cycles.push(cycle.to_vec());
+            }
+        }
+        cycles
+    }
+
+    fn chordless_cycles(&self) -> Vec<Vec<Self::NodeId>> {
+        // position of each node in the current cycle
+        let mut pos = vec![usize::MAX; self.node_bound()];
+        let mut cycles = Vec::new();
+        self.visit_all_cycles(|g, cycle| {
+            for (i, &n) in cycle.iter().enumerate() {
+                pos[g.to_index(n)] = i;
+            }
+            let len = cycle.len();
+            let has_chord = cycle.iter().enumerate().any(|(i, &n)| {
+                g.neighbors(n).any(|w| {
+                    let j = pos[g.to_index(w)];
+                    j != usize::MAX
+                        && j != i
+                        && j != (i + 1) % len
+                        && i != (j + 1) % len
+                })
+            });
+            for &n in cycle {
+                pos[g.to_index(n)] = usize::MAX;
+            }
+            if !has_chord {
+                cycles.push(cycle.to_vec());
+            }
+        });
+        cycles
+    }
+
+    fn undirected_cycles(&self) -> Vec<Vec<Self::NodeId>>
+    where
+        for<'a> &'a Self: IntoNeighborsDirected,
+    {
+        // after ignoring the edge directions, the search never leaves
+        // the connected component of the start node
+        let nodes = self.node_identifiers().collect();
+        let mut finder: Finder<_, _> = Finder::default();
+        finder.reset_for_undirected(self, nodes);
+        let mut cycles = Vec::new();
+        while let Some((cycle, _)) = finder.next_cycle() {
+            cycles.push(cycle.to_vec());
+        }
+        cycles
+    }
+
+    fn cycles_canonical(&self) -> Vec<Vec<Self::NodeId>>
+    where
+        Self::NodeId: Ord,
+    {
+        let undirected = !self.is_directed();
+        let mut cycles = self.cycles();
+        for cycle in &mut cycles {
+            cycle::canonicalise(cycle, undirected);
+        }
+        cycles
+    }
+
+    fn visit_cycles_indices<F, B>(&self, mut visitor: F) -> Option<B>
+    where
+        F: FnMut(&G, &[usize]) -> ControlFlow<B>,
+    {
+        let mut indices = Vec::new();
+        self.visit_cycles(|g, cycle| {
+            indices.clear();
+            indices.extend(cycle.iter().map(|&n| g.to_index(n)));
+            visitor(g, &indices)
+        })
+    }
+
+    fn cycles_raw(&self) -> Vec<Cycle> {
+        let mut cycles = Vec::new();
+        self.visit_all_cycles(|g, cycle| {
+            cycles.push(Cycle(cycle.iter().map(|&n| g.to_index(n)).collect()))
+        });
+        cycles
+    }
+
+    fn cycles_iter(&self) -> CyclesIter<'_, Self> {
+        CyclesIter::new(self, cyclic_components(self).collect())
+    }
+
+    fn is_on_any_cycle(&self, node: Self::NodeId) -> bool {
+        if self.neighbors(node).any(|n| n == node) {
+            return true;
+        }
+        if self.is_directed() {
+            tarjan_scc(self).into_iter().any(|component| {
+                component.len() > 1 && component.contains(&node)
+            })
+        } else {
+            bridges::is_on_undirected_cycle(self, node)
+        }
+    }
+
+    fn girth(&self) -> Option<usize> {
+        shortest::girth(self)
+    }
+
+    fn shortest_cycle(&self) -> Option<Vec<Self::NodeId>> {
+        shortest::shortest_cycle(self)
+    }
+
+    fn fundamental_cycle_basis(&self) -> Vec<Vec<Self::NodeId>> {
+        basis::fundamental_cycle_basis(self)
+    }
+}
+
+/// Trait for identifying cycles in a graph in terms of their edges
+///
+/// In contrast to [Cycles], this allows to distinguish between cycles
+/// that only differ in the choice of parallel edges. The trait is
+/// implemented for every graph type `G` such that `&G` implements
+/// petgraph's [IntoEdges], [IntoNodeIdentifiers], [NodeIndexable],
+/// and [GraphProp] visitor traits.
+///
+/// In undirected graphs, each cycle is only reported in one
+/// orientation. Two distinct edges between the same nodes form a
+/// cycle.
+pub trait EdgeCycles: Cycles {
+    /// The edge identifier of the underlying graph
+    type EdgeId: Copy + PartialEq;
+
+    /// Apply the `visitor` to each cycle until we are told to stop
+    ///
+    /// The first argument passed to the visitor is a reference to the
+    /// graph and the second one a slice with all edges that form the
+    /// cycle, in the order in which they are traversed. If at any
+    /// point the visitor returns `ControlFlow::Break(b)` this function
+    /// stops visiting any further cycles and returns `Some(b)`.
+    /// Otherwise the return value is `None`.
+    fn visit_cycles_edges<F, B>(&self, visitor: F) -> Option<B>
+    where
+        F: FnMut(&Self, &[Self::EdgeId]) -> ControlFlow<B>;
+
+    /// Apply the `visitor` to the nodes and edges of each cycle until
+    /// we are told to stop
+    ///
+    /// Apart from the additional slice with the nodes of the cycle,
+    /// this is the same as
+    /// [visit_cycles_edges](EdgeCycles::visit_cycles_edges). The two
+    /// slices have the same length and the `i`th edge connects the
+    /// `i`th node to the next one, wrapping around at the end.
+    fn visit_cycles_full<F, B>(&self, visitor: F) -> Option<B>
+    where
+        F: FnMut(&Self, &[Self::NodeId], &[Self::EdgeId]) -> ControlFlow<B>;
+
+    /// Apply the `visitor` to each cycle in the subgraph of allowed
+    /// edges until we are told to stop
+    ///
+    /// An edge is only used if `edge_ok` returns `true` for its
+    /// identifier. Apart from that, this is the same as
+    /// [visit_cycles](Cycles::visit_cycles). In particular, the
+    /// visitor is passed the nodes of each cycle. The graph is not
+    /// copied, but `edge_ok` may be called several times for the same
+    /// edge.
+    fn visit_cycles_filtered<P, F, B>(
+        &self,
+        edge_ok: P,
+        visitor: F,
+    ) -> Option<B>
+    where
+        P: FnMut(Self::EdgeId) -> bool,
+        F: FnMut(&Self, &[Self::NodeId]) -> ControlFlow<B>;
+
+    /// Apply the `visitor` to each cycle with edge directions decided
+    /// by `forward` until we are told to stop
+    ///
+    /// Each edge can only be traversed from its source to its target
+    /// if `forward` returns `true` for its identifier, and only in the
+    /// opposite direction otherwise. Source and target are as given by
+    /// petgraph's [EdgeRef], so this also works for undirected graphs,
+    /// whose edges are all oriented by `forward`. Apart from that, this
+    /// is the same as [visit_cycles](Cycles::visit_cycles).
+    /// `forward` is called exactly once for each edge, before the
+    /// search starts.
+    fn visit_cycles_directed_by<D, F, B>(
+        &self,
+        forward: D,
+        visitor: F,
+    ) -> Option<B>
+    where
+        D: FnMut(Self::EdgeId) -> bool,
+        F: FnMut(&Self, &[Self::NodeId]) -> ControlFlow<B>;
+
+    /// Find all cycles in terms of their edges
+    ///
+    /// Each element of the returned `Vec` is a `Vec` of all edges in
+    /// one cycle.
+    fn cycles_edges(&self) -> Vec<Vec<Self::EdgeId>>;
+
+    /// Find the cycle with the smallest total edge weight
+    ///
+    /// The `weight` of each edge is determined by the passed closure.
+    /// Returns the edges of the cycle with the minimum sum of edge
+    /// weights together with this sum, or `None` if there are no
+    /// cycles. Cycles with a `NaN` total weight are never chosen. If
+    /// there are several cycles with the same minimum weight, the
+    /// first one found is returned.
+    ///
+    /// This enumerates all cycles, so the run time is exponential in
+    /// the worst case.
+    fn min_weight_cycle_by<W>(
+        &self,
+        mut weight: W,
+    ) -> Option<(Vec<Self::EdgeId>, f64)>
+    where
+        W: FnMut(Self::EdgeId) -> f64,
+    {
+        let mut min: Option<(Vec<Self::EdgeId>, f64)> = None;
+        self.visit_cycles_edges(|_, cycle| {
+            let total: f64 = cycle.iter().map(|&e| weight(e)).sum();
+            if min.as_ref().map_or(!total.is_nan(), |(_, w)| total < *w) {
+                min = Some((cycle.to_vec(), total));
+            }
+            ControlFlow::<()>::Continue(())
+        });
+        min
+    }
+
+    /// Find all cycles together with their total edge weight
+    ///
+    /// The `weight` of each edge is determined by the passed closure.
+    /// Each element of the returned `Vec` contains the nodes of one
+    /// cycle and the sum of the weights of the edges along it. Like for
+    /// [cycles_edges](EdgeCycles::cycles_edges), cycles along
+    /// different parallel edges are distinct, so the same sequence of
+    /// nodes can appear several times with different weights.
+    fn cycles_with_total_weight_by<W>(
+        &self,
+        weight: W,
+    ) -> Vec<(Vec<Self::NodeId>, f64)>
+    where
+        W: FnMut(Self::EdgeId) -> f64;
+
+    /// Find all cycles with a total edge weight between `lo` and `hi`
+    ///
+    /// Apart from only returning the cycles with a total weight in the
+    /// inclusive range from `lo` to `hi`, this is the same as
+    /// [cycles_with_total_weight_by](EdgeCycles::cycles_with_total_weight_by).
+    /// The weight of each path is accumulated during the search. If
+    /// all weights are nonnegative, paths that are already heavier
+    /// than `hi` are not extended, which can make this much faster
+    /// than filtering all cycles. With negative weights, a path can
+    /// become lighter again, so in this case every cycle is visited.
+    fn cycles_with_edge_weights_sum_in_range<W>(
+        &self,
+        lo: f64,
+        hi: f64,
+        weight: W,
+    ) -> Vec<(Vec<Self::NodeId>, f64)>
+    where
+        W: FnMut(Self::EdgeId) -> f64;
+
+    /// Find a cycle with negative total edge weight
+    ///
+    /// The `weight` of each edge is determined by the passed closure.
+    /// Returns the nodes of any cycle for which the sum of edge
+    /// weights is negative, or `None` if there is no such cycle. In
+    /// directed graphs, this uses the Bellman-Ford algorithm with a
+    /// run time of O(VE). In undirected graphs, we enumerate cycles
+    /// until we find a negative one, which takes exponential time in
+    /// the worst case.
+    fn find_negative_cycle_by<W>(&self, weight: W) -> Option<Vec<Self::NodeId>>
+    where
+        W: FnMut(Self::EdgeId) -> f64;
+
+    /// Find all cycles that traverse `edge`
+    ///
+    /// Each element of the returned `Vec` is a `Vec` of all nodes in
+    /// one cycle. It starts with the source of `edge`, followed by its
+    /// target. Cycles that only use a parallel edge are not included.
+    /// In undirected graphs, source and target are the endpoints as
+    /// given by petgraph's [EdgeRef].
+    fn cycles_through_edge(&self, edge: Self::EdgeId)
+        -> Vec<Vec<Self::NodeId>>;
+
+    /// Check whether there is a cycle traversing all `edges`
+    ///
+    /// Only cycles through the first edge are searched and the search
+    /// stops as soon as a cycle with all edges is found. If the edges
+    /// are not all in the same strongly connected component, there is
+    /// no such cycle and the search is skipped entirely. Without any
+    /// edges, this is the same as [has_cycle](Cycles::has_cycle).
+    fn contains_cycle_through_all_edges(&self, edges: &[Self::EdgeId]) -> bool;
+
+    /// Find all edges that lie on at least one cycle
+    ///
+    /// In directed graphs, these are the edges within a strongly
+    /// connected component. In undirected graphs, these are the edges
+    /// that are not bridges. Self-loops always lie on a cycle. The
+    /// edges are returned in the order of petgraph's
+    /// `edge_references` and the run time is linear in the graph
+    /// size.
+    fn edges_on_cycles(&self) -> Vec<Self::EdgeId>;
+
+    /// Find all edges that do not lie on any cycle
+    ///
+    /// These are exactly the edges not returned by
+    /// [edges_on_cycles](EdgeCycles::edges_on_cycles). In directed
+    /// graphs, they connect different strongly connected components.
+    /// In undirected graphs, they are the bridges. Like for
+    /// [edges_on_cycles](EdgeCycles::edges_on_cycles), the edges are
+    /// returned in the order of petgraph's `edge_references` and the
+    /// run time is linear in the graph size.
+    fn edges_not_on_any_cycle(&self) -> Vec<Self::EdgeId>;
+}
+
+impl<G> EdgeCycles for G
+where
+    G: GraphBase,
+    for<'a> &'a G: GraphBase<NodeId = G::NodeId, EdgeId = G::EdgeId>
+        + IntoEdges
+        + IntoNodeIdentifiers
+        + NodeIndexable
+        + GraphProp,
+{
+    type EdgeId = G::EdgeId;
+
+    fn visit_cycles_edges<F, B>(&self, mut visitor: F) -> Option<B>
+    where
+        F: FnMut(&G, &[Self::EdgeId]) -> ControlFlow<B>,
+    {
+        self.visit_cycles_full(|g, _, edges| visitor(g, edges))
+    }
+
+    fn visit_cycles_full<F, B>(&self, mut visitor: F) -> Option<B>
+    where
+        F: FnMut(&G, &[Self::NodeId], &[Self::EdgeId]) -> ControlFlow<B>,
+    {
+        for component in cyclic_components(self) {
+            let mut finder = Finder::with_edges(self, component);
+            if let ControlFlow::Break(b) =
+                finder.visit(&mut |nodes, edges| visitor(self, nodes, edges))
+            {
+                return Some(b);
+            }
+        }
+        None
+    }
+
+    fn visit_cycles_filtered<P, F, B>(
+        &self,
+        edge_ok: P,
+        mut visitor: F,
+    ) -> Option<B>
+    where
+        P: FnMut(Self::EdgeId) -> bool,
+        F: FnMut(&G, &[Self::NodeId]) -> ControlFlow<B>,
+    {
+        let edge_ok = RefCell::new(edge_ok);
+        let filtered = EdgeFiltered::from_fn(
+            self,
+            |e: <&G as IntoEdgeReferences>::EdgeRef| {
+                (edge_ok.borrow_mut())(e.id())
+            },
+        );
+        // the strongly connected components also depend on the filter
+        let mut finder: Finder<_, _> = Finder::default();
+        for component in cyclic_components(&filtered) {
+            finder.reset_for(&filtered, component);
+            if let ControlFlow::Break(b) =
+                finder.visit(&mut |cycle, _| visitor(self, cycle))
+            {
+                return Some(b);
+            }
+        }
+        None
+    }
+
+    fn visit_cycles_directed_by<D, F, B>(
+        &self,
+        mut forward: D,
+        mut visitor: F,
+    ) -> Option<B>
+    where
+        D: FnMut(Self::EdgeId) -> bool,
+        F: FnMut(&G, &[Self::NodeId]) -> ControlFlow<B>,
+    {
+        // oriented copy of the graph in terms of node indices
+        let mut oriented = List::<(), usize>::with_capacity(self.node_bound());
+        for _ in 0..self.node_bound() {
+            oriented.add_node();
+        }
+        for e in self.edge_references() {
+            let source = self.to_index(e.source());
+            let target = self.to_index(e.target());
+            if forward(e.id()) {
+                oriented.add_edge(source, target, ());
+            } else {
+                oriented.add_edge(target, source, ());
+            }
+        }
+        let mut nodes = Vec::new();
+        oriented.visit_cycles(|_, cycle| {
+            nodes.clear();
+            nodes.extend(cycle.iter().map(|&i| self.from_index(i)));
+            visitor(self, &nodes)
+        })
+    }
+
+    fn cycles_edges(&self) -> Vec<Vec<Self::EdgeId>> {
+        let mut cycles = Vec::new();
+        self.visit_cycles_edges(|_, cycle| {
+            cycles.push(cycle.to_vec());
+            ControlFlow::<()>::Continue(())
+        });
+        cycles
+    }
+
+    fn cycles_with_total_weight_by<W>(
+        &self,
+        mut weight: W,
+    ) -> Vec<(Vec<Self::NodeId>, f64)>
+    where
+        W: FnMut(Self::EdgeId) -> f64,
+    {
+        let mut cycles = Vec::new();
+        for component in cyclic_components(self) {
+            let mut finder = Finder::with_edges(self, component);
+            while let Some((nodes, edges)) = finder.next_cycle() {
+                let total = edges.iter().map(|&e| weight(e)).sum();
+                cycles.push((nodes.to_vec(), total));
+            }
+        }
+        cycles
+    }
+
+    fn cycles_with_edge_weights_sum_in_range<W>(
+        &self,
+        lo: f64,
+        hi: f64,
+        mut weight: W,
+    ) -> Vec<(Vec<Self::NodeId>, f64)>
+    where
+        W: FnMut(Self::EdgeId) -> f64,
+    {
+        // pruning heavy paths is only correct if they can't get lighter
+        let nonnegative = self.edge_references().all(|e| weight(e.id()) >= 0.);
+        let mut cycles = Vec::new();
+        for component in cyclic_components(self) {
+            let mut finder = Finder::with_edges(self, component);
+            if nonnegative {
+                finder.set_max_weight(&mut weight, hi);
+            }
+            while let Some((nodes, edges)) = finder.next_cycle() {
+                let total = edges.iter().map(|&e| weight(e)).sum();
+                if (lo..=hi).contains(&total) {
+                    cycles.push((nodes.to_vec(), total));
+                }
+            }
+        }
+        cycles
+    }
+
+    fn find_negative_cycle_by<W>(
+        &self,
+        mut weight: W,
+    ) -> Option<Vec<Self::NodeId>>
+    where
+        W: FnMut(Self::EdgeId) -> f64,
+    {
+        if self.is_directed() {
+            return negative::negative_cycle(self, weight);
+        }
+        // Bellman-Ford would go back and forth along negative edges
+        for component in cyclic_components(self) {
+            let mut finder = Finder::with_edges(self, component);
+            while let Some((nodes, edges)) = finder.next_cycle() {
+                let total: f64 = edges.iter().map(|&e| weight(e)).sum();
+                if total < 0. {
+                    return Some(nodes.to_vec());
+                }
+            }
+        }
+        None
+    }
+
+    fn cycles_through_edge(
+        &self,
+        edge: Self::EdgeId,
+    ) -> Vec<Vec<Self::NodeId>> {
+        let mut cycles = Vec::new();
+        let Some(source) = self
+            .edge_references()
+            .find(|e| e.id() == edge)
+            .map(|e| e.source())
+        else {
+            return cycles;
+        };
+        let Some(mut component) = tarjan_scc(self)
+            .into_iter()
+            .find(|component| component.contains(&source))
+        else {
+            return cycles;
+        };
+        let pos = component.iter().position(|&n| n == source).unwrap();
+        component.swap(0, pos);
+        let mut finder = Finder::with_edges(self, component)
+            .with_roots(1)
+            .with_first_edge(edge);
+        while let Some((cycle, _)) = finder.next_cycle() {
+            cycles.push(cycle.to_vec());
+        }
+        cycles
+    }
+
+    fn contains_cycle_through_all_edges(&self, edges: &[Self::EdgeId]) -> bool {
+        let Some((&first, rest)) = edges.split_first() else {
+            return self.has_cycle();
+        };
+        let mut endpoints = Vec::with_capacity(2 * edges.len());
+        for &edge in edges {
+            let Some(e) = self.edge_references().find(|e| e.id() == edge)
+            else {
+                return false;
+            };
+            endpoints.extend([e.source(), e.target()]);
+        }
+        let source = endpoints[0];
+        let Some(mut component) = tarjan_scc(self)
+            .into_iter()
+            .find(|component| component.contains(&source))
+        else {
+            return false;
+        };
+        if !endpoints.iter().all(|n| component.contains(n)) {
+            return false;
+        }
+        let pos = component.iter().position(|&n| n == source).unwrap();
+        component.swap(0, pos);
+        let num_distinct = edges
+            .iter()
+            .enumerate()
+            .filter(|&(i, e)| !edges[..i].contains(e))
+            .count();
+        let mut finder = Finder::with_edges(self, component)
+            .with_roots(1)
+            .with_first_edge(first);
+        finder.set_len_bounds(num_distinct, usize::MAX);
+        while let Some((_, cycle)) = finder.next_cycle() {
+            if rest.iter().all(|e| cycle.contains(e)) {
+                return true;
+            }
+        }
+        false
+    }
+
+    fn edges_on_cycles(&self) -> Vec<Self::EdgeId> {
+        if !self.is_directed() {
+            return bridges::undirected_edges_on_cycles(self);
+        }
+        let component = component_indices(self);
+        self.edge_references()
+            .filter(|e| {
+                component[self.to_index(e.source())]
+                    == component[self.to_index(e.target())]
+            })
+            .map(|e| e.id())
+            .collect()
+    }
+
+    fn edges_not_on_any_cycle(&self) -> Vec<Self::EdgeId> {
+        if !self.is_directed() {
+            return bridges::undirected_bridges(self);
+        }
+        let component = component_indices(self);
+        self.edge_references()
+            .filter(|e| {
+                component[self.to_index(e.source())]
+                    != component[self.to_index(e.target())]
+            })
+            .map(|e| e.id())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{
+        arb_graph, arb_ungraph, brute_force_cycles, brute_force_cycles_edges,
+        random_graphs,
+    };
+
+    use petgraph::{
+        adj::List,
+        csr::Csr,
+        graph::{EdgeIndex, Graph, NodeIndex, UnGraph},
+        graphmap::{DiGraphMap, UnGraphMap},
+        matrix_graph::MatrixGraph,
+        stable_graph::StableGraph,
+    };
+
+    // rotate each cycle to start with its smallest node and sort the cycles
+    fn normalised<N: Copy + Ord>(mut cycles: Vec<Vec<N>>) -> Vec<Vec<N>> {
+        for cycle in &mut cycles {
+            let start = (0..cycle.len()).min_by_key(|&i| cycle[i]).unwrap();
+            cycle.rotate_left(start);
+        }
+        cycles.sort();
+        cycles
+    }
+
+    #[test]
+    fn test() {}
+
+    #[test]
+    fn graph_with_borrowed_weights() {
+        let labels = ["a".to_string(), "b".to_string()];
+        let mut g = Graph::<&str, ()>::new();
+        let a = g.add_node(&labels[0]);
+        let b = g.add_node(&labels[1]);
+        g.extend_with_edges([(a, b), (b, a)]);
+        let cycles = g.cycles();
+        assert_eq!(cycles.len(), 1);
+        assert!(cycles[0] == [a, b] || cycles[0] == [b, a]);
+    }
+
+    #[test]
+    fn into_cycles() {
+        let mut g = Graph::<String, ()>::new();
+        let a = g.add_node("a".to_string());
+        let b = g.add_node("b".to_string());
+        let c = g.add_node("c".to_string());
+        g.extend_with_edges([(a, b), (b, c), (c, a), (c, c)]);
+        let expected = g.cycles_weights().into_iter().map(|cycle| {
+            cycle.into_iter().map(|w| w.to_owned()).collect::<Vec<_>>()
+        });
+        let expected: Vec<_> = expected.collect();
+
+        let (cycles, weights) = g.into_cycles();
+        let labels: Vec<Vec<_>> = cycles
+            .iter()
+            .map(|cycle| cycle.iter().map(|&n| weights[n].clone()).collect())
+            .collect();
+        assert_eq!(labels, expected);
+        assert_eq!(weights, ["a", "b", "c"]);
+    }
+
+    #[test]
+    fn adjacency_list() {
+        let mut g = List::<()>::new();
+        for _ in 0..4 {
+            g.add_node();
+        }
+        for (from, to) in [(0, 1), (1, 2), (2, 0), (2, 3)] {
+            g.add_edge(from, to, ());
+        }
+        assert_eq!(normalised(g.cycles()), [[0, 1, 2]]);
+    }
+
+    #[test]
+    fn stable_graph_with_removed_node() {
+        let mut g = StableGraph::<(), ()>::from_edges([
+            (0, 1),
+            (1, 0),
+            (1, 2),
+            (2, 3),
+            (3, 1),
+            (3, 4),
+            (4, 3),
+        ]);
+        assert_eq!(g.cycles().len(), 3);
+
+        g.remove_node(2.into());
+        assert_eq!(
+            normalised(g.cycles()),
+            [[0.into(), 1.into()], [3.into(), 4.into()]]
+        );
+
+        g.add_edge(1.into(), 4.into(), ());
+        g.add_edge(4.into(), 1.into(), ());
+        assert_eq!(
+            normalised(g.cycles()),
+            [
+                vec![0.into(), 1.into()],
+                vec![1.into(), 4.into()],
+                vec![1.into(), 4.into(), 3.into()],
+                vec![3.into(), 4.into()],
+            ]
+        );
+    }
+
+    #[test]
+    fn matrix_graph() {
+        let mut g = MatrixGraph::<(), ()>::with_capacity(4);
+        let nodes: Vec<_> = (0..4).map(|_| g.add_node(())).collect();
+        for &a in &nodes {
+            for &b in &nodes {
+                if a != b {
+                    g.add_edge(a, b, ());
+                }
+            }
+        }
+        // 6 cycles of length 2, 8 of length 3, and 6 of length 4
+        assert_eq!(g.cycles().len(), 20);
+        assert_eq!(g.cycles_edges().len(), 20);
+        assert_eq!(g.count_cycles_of_length(3), 8);
+
+        g.remove_node(nodes[3]);
+        assert_eq!(
+            normalised(g.cycles()),
+            [
+                vec![nodes[0], nodes[1]],
+                vec![nodes[0], nodes[1], nodes[2]],
+                vec![nodes[0], nodes[2]],
+                vec![nodes[0], nodes[2], nodes[1]],
+                vec![nodes[1], nodes[2]],
+            ]
+        );
+    }
+
+    #[test]
+    fn csr() {
+        for g in random_graphs(100, 10, 30) {
+            let mut edges: Vec<_> = g
+                .edge_references()
+                .map(|e| (e.source().index() as u32, e.target().index() as u32))
+                .collect();
+            edges.sort();
+            // parallel edges are not supported by `Csr`
+            edges.dedup();
+            let mut csr = Csr::<(), ()>::from_sorted_edges(&edges).unwrap();
+            // nodes without outgoing edges may be missing
+            while csr.node_count() < g.node_count() {
+                csr.add_node(());
+            }
+            let expected: Vec<Vec<u32>> = g
+                .cycles()
+                .into_iter()
+                .map(|c| c.into_iter().map(|n| n.index() as u32).collect())
+                .collect();
+            let expected_len = expected.len();
+            assert_eq!(normalised(csr.cycles()), normalised(expected));
+            assert_eq!(csr.cycles_edges().len(), expected_len);
+        }
+    }
+
+    #[test]
+    fn graph_map() {
+        let g = DiGraphMap::<&str, ()>::from_edges([
+            ("a", "b"),
+            ("b", "c"),
+            ("c", "a"),
+            ("c", "d"),
+            ("d", "c"),
+            ("d", "e"),
+        ]);
+        assert_eq!(
+            normalised(g.cycles()),
+            [vec!["a", "b", "c"], vec!["c", "d"]]
+        );
+    }
+
+    #[test]
+    fn index_mapping() {
+        // neighbours are mapped to positions in their component via
+        // `to_index`; insert the nodes of a graph map in reverse order
+        // with scrambled labels, so that neither the labels nor the
+        // insertion order agree with the original node indices
+        let label = |n: NodeIndex| (n.index() as u32 * 7919) % 10007 + 100;
+        for g in random_graphs(500, 8, 16) {
+            let mut map = DiGraphMap::<u32, ()>::new();
+            for n in g.node_indices().rev() {
+                map.add_node(label(n));
+            }
+            for e in g.edge_references() {
+                map.add_edge(label(e.source()), label(e.target()), ());
+            }
+            let expected: Vec<Vec<_>> = brute_force_cycles(&g)
+                .into_iter()
+                .map(|c| c.into_iter().map(label).collect())
+                .collect();
+            assert_eq!(normalised(map.cycles()), normalised(expected));
+        }
+    }
+
+    #[test]
+    fn small_index_type() {
+        // `u8` indices allow at most 255 nodes and edges, the last one
+        // has index 254
+        let ring = (0..200).map(|i| (i, (i + 1) % 200));
+        let chords = [(199, 254), (254, 254), (254, 0), (100, 0), (3, 1)];
+        let edges: Vec<(u8, u8)> = ring.chain(chords).collect();
+        let small = Graph::<(), (), petgraph::Directed, u8>::from_edges(&edges);
+        assert_eq!(small.node_count(), 255);
+        let large = Graph::<(), ()>::from_edges(
+            edges.iter().map(|&(a, b)| (a as u32, b as u32)),
+        );
+        assert_eq!(small.cycles_indices(), large.cycles_indices());
+        assert_eq!(small.cycles_indices().len(), 5);
+        fn edge_indices<Ix: petgraph::graph::IndexType>(
+            cycles: Vec<Vec<EdgeIndex<Ix>>>,
+        ) -> Vec<Vec<usize>> {
+            cycles
+                .into_iter()
+                .map(|c| c.into_iter().map(|e| e.index()).collect())
+                .collect()
+        }
+        assert_eq!(
+            edge_indices(small.cycles_edges()),
+            edge_indices(large.cycles_edges())
+        );
+        assert_eq!(
+            small.fundamental_cycle_basis().len(),
+            large.fundamental_cycle_basis().len()
+        );
+        assert!(small.is_on_any_cycle(NodeIndex::new(254)));
+
+        let mut stable =
+            StableGraph::<(), (), petgraph::Directed, u8>::from_edges(&edges);
+        stable.remove_node(NodeIndex::new(150));
+        let cycles = stable.cycles_indices();
+        assert_eq!(
+            normalised(cycles),
+            [(0..=100).collect(), vec![1, 2, 3], vec![254]]
+        );
+    }
+
+    #[test]
+    fn merge_equivalent_cycles() {
+        let triangles =
+            vec![vec![1, 2, 3], vec![3, 2, 1], vec![2, 3, 1], vec![1, 3, 2]];
+        assert_eq!(crate::merge_equivalent_cycles(triangles), [vec![1, 2, 3]]);
+        let cycles = vec![
+            vec![4],
+            vec![5, 4],
+            vec![4, 5],
+            vec![2, 1, 4, 3],
+            vec![1, 2, 4, 3],
+            vec![4, 3, 1, 2],
+        ];
+        assert_eq!(
+            crate::merge_equivalent_cycles(cycles),
+            [vec![1, 2, 3, 4], vec![1, 2, 4, 3], vec![4], vec![4, 5]]
+        );
+
+        // both orientations of each cycle in a symmetric directed graph
+        let g = UnGraph::<(), ()>::from_edges([
+            (0, 1),
+            (1, 2),
+            (2, 0),
+            (2, 3),
+            (3, 0),
+        ]);
+        let symmetric =
+            Graph::<(), ()>::from_edges(g.edge_references().flat_map(|e| {
+                [(e.source(), e.target()), (e.target(), e.source())]
+            }));
+        let directed: Vec<_> = symmetric
+            .cycles()
+            .into_iter()
+            .filter(|c| c.len() > 2)
+            .collect();
+        assert_eq!(directed.len(), 6);
+        assert_eq!(
+            crate::merge_equivalent_cycles(directed),
+            crate::merge_equivalent_cycles(g.cycles())
+        );
+    }
+
+    #[test]
+    fn cycles_iter() {
+        let g = Graph::<(), ()>::from_edges([
+            (0, 1),
+            (1, 0),
+            (0, 2),
+            (2, 0),
+            (1, 2),
+            (2, 1),
+            (2, 3),
+            (3, 2),
+            (4, 5),
+            (5, 4),
+        ]);
+        let cycles = g.cycles();
+        assert_eq!(cycles.len(), 7);
+        assert_eq!(g.cycles_iter().collect::<Vec<_>>(), cycles);
+        assert_eq!(g.cycles_iter().take(3).collect::<Vec<_>>(), cycles[..3]);
+        assert_eq!(
+            g.cycles_iter().find(|c| c.len() == 3),
+            cycles.iter().find(|c| c.len() == 3).cloned()
+        );
+    }
+
+    #[test]
+    fn cycles_weights_iter() {
+        let mut g = Graph::<String, ()>::new();
+        let nodes: Vec<_> = ["a", "b", "c", "d"]
+            .map(|w| g.add_node(w.to_owned()))
+            .into();
+        g.extend_with_edges([
+            (nodes[0], nodes[1]),
+            (nodes[1], nodes[0]),
+            (nodes[1], nodes[2]),
+            (nodes[2], nodes[0]),
+            (nodes[3], nodes[3]),
+        ]);
+        let labels = |weights: Vec<&String>| -> String {
+            weights.into_iter().map(String::as_str).collect()
+        };
+        let expected: Vec<_> =
+            g.cycles_weights().into_iter().map(labels).collect();
+        assert_eq!(expected.len(), 3);
+        let mut iter = g.cycles_weights_iter();
+        let first = iter.next().unwrap();
+        let rest: Vec<_> = iter.map(labels).collect();
+        // the items borrow from the graph, not from the iterator
+        assert_eq!(labels(first), expected[0]);
+        assert_eq!(rest, expected[1..]);
+    }
+
+    #[test]
+    fn reset_between_roots() {
+        // With the start vertices in the order 0, 1, 2, 3, both 2 and 3
+        // remain blocked after starting from 1, since the only way
+        // back is through 0. Unless they are unblocked again, we miss
+        // the cycle 2 -> 3 -> 2.
+        let g = Graph::<(), ()>::from_edges([
+            (0, 1),
+            (1, 2),
+            (2, 0),
+            (2, 3),
+            (3, 2),
+        ]);
+        let mut finder = CycleFinder::new(&g, g.node_indices().collect());
+        let mut cycles = Vec::new();
+        while let Some(cycle) = finder.next_cycle() {
+            cycles.push(cycle.iter().map(|n| n.index()).collect::<Vec<_>>());
+        }
+        assert_eq!(cycles, [vec![0, 1, 2], vec![2, 3]]);
+
+        // the same with the start vertices in different orders
+        for g in random_graphs(100, 10, 30) {
+            let mut cycles = Vec::new();
+            for mut component in petgraph::algo::tarjan_scc(&g) {
+                component.reverse();
+                let mut finder = CycleFinder::new(&g, component);
+                while let Some(cycle) = finder.next_cycle() {
+                    cycles.push(cycle.to_vec());
+                }
+            }
+            assert_eq!(normalised(cycles), normalised(brute_force_cycles(&g)));
+        }
+    }
+
+    #[test]
+    fn forced_blists() {
+        for g in random_graphs(100, 10, 30) {
+            let expected = g.cycles();
+            for dense in [Some(false), Some(true), None] {
+                let mut cycles = Vec::new();
+                let mut finder: CycleFinder<_> = CycleFinder::default();
+                finder.set_dense_blists(dense);
+                for component in cyclic_components(&g) {
+                    finder.reset_for(&g, component);
+                    while let Some(cycle) = finder.next_cycle() {
+                        cycles.push(cycle.to_vec());
+                    }
+                }
+                assert_eq!(cycles, expected);
+            }
+        }
+    }
+
+    #[test]
+    fn large_dag() {
+        const N: u32 = 10_000;
+        let edges = (1..N).flat_map(|i| [(i / 2, i), (i - 1, i)]);
+        let mut g = Graph::<(), ()>::from_edges(edges);
+        assert!(!g.has_cycle());
+        assert!(g.cycles().is_empty());
+        assert_eq!(g.cycles_iter().next(), None);
+        assert!(g.cycles_edges().is_empty());
+        assert!(CycleEnumerator::new(&g).all().is_empty());
+        g.add_edge((N - 1).into(), (N - 1).into(), ());
+        assert_eq!(g.cycles(), [vec![(N - 1).into()]]);
+        assert_eq!(g.cycles_edges().len(), 1);
+    }
+
+    #[test]
+    fn long_cycle() {
+        const N: u32 = 100_000;
+        let g = Graph::<(), ()>::from_edges(
+            (1..N).map(|i| (i - 1, i)).chain([(N - 1, 0)]),
+        );
+        let cycles = g.cycles();
+        assert_eq!(cycles.len(), 1);
+        assert_eq!(cycles[0].len(), N as usize);
+    }
+
+    #[test]
+    fn deep_unblock() {
+        const N: u32 = 100_000;
+        // The search starts from N + 1 and first explores the path
+        // 0 -> 1 -> ... -> N -> 0 without finding a cycle. Each node
+        // on the path remains blocked until the cycle N + 1 -> 0 -> N + 1
+        // is found, which unblocks the whole path one node at a time.
+        let path = (0..N).map(|i| (i, i + 1));
+        let g = Graph::<(), ()>::from_edges(path.chain([
+            (N, 0),
+            (0, N + 1),
+            (N + 1, 0),
+        ]));
+        let mut lengths: Vec<_> = g.cycles().iter().map(|c| c.len()).collect();
+        lengths.sort_unstable();
+        assert_eq!(lengths, [2, N as usize + 1]);
+    }
+
+    #[test]
+    fn cycle_finder_hasher() {
+        use std::collections::hash_map::RandomState;
+
+        // sparse enough to store the B-lists in hash sets
+        const N: u32 = 200;
+        let ring = (1..N).map(|i| (i - 1, i)).chain([(N - 1, 0)]);
+        let back_edges = (1..N).step_by(50).map(|i| (i, i - 1));
+        let g = Graph::<(), ()>::from_edges(ring.chain(back_edges));
+        let mut cycles = Vec::new();
+        let mut finder: CycleFinder<_, RandomState> = CycleFinder::default();
+        for component in petgraph::algo::tarjan_scc(&g) {
+            finder.reset_for(&g, component);
+            while let Some(cycle) = finder.next_cycle() {
+                cycles.push(cycle.to_vec());
+            }
+        }
+        assert_eq!(cycles.len(), 5);
+        assert_eq!(normalised(cycles), normalised(g.cycles()));
+    }
+
+    #[test]
+    fn deterministic_order() {
+        use std::collections::hash_map::RandomState;
+
+        // mix of sparse and dense components, so that both kinds of
+        // B-lists are used
+        let mut graphs = random_graphs(100, 10, 30);
+        const N: u32 = 200;
+        let ring = (1..N).map(|i| (i - 1, i)).chain([(N - 1, 0)]);
+        let back_edges = (1..N).step_by(20).map(|i| (i, i - 1));
+        graphs.push(Graph::from_edges(ring.chain(back_edges)));
+        for g in graphs {
+            let cycles = g.cycles();
+            assert_eq!(g.clone().cycles(), cycles);
+            let mut with_std_hasher = Vec::new();
+            let mut finder: CycleFinder<_, RandomState> =
+                CycleFinder::default();
+            for component in cyclic_components(&g) {
+                finder.reset_for(&g, component);
+                while let Some(cycle) = finder.next_cycle() {
+                    with_std_hasher.push(cycle.to_vec());
+                }
+            }
+            assert_eq!(with_std_hasher, cycles);
+        }
+    }
+
+    #[test]
+    fn large_component() {
+        const N: u32 = 5_000;
+        let ring = (1..N).map(|i| (i - 1, i)).chain([(N - 1, 0)]);
+        let back_edges = (1..N).step_by(100).map(|i| (i, i - 1));
+        let g = Graph::<(), ()>::from_edges(ring.chain(back_edges));
+        let cycles = g.cycles();
+        assert_eq!(cycles.len(), 51);
+        assert_eq!(cycles.iter().filter(|c| c.len() == 2).count(), 50);
+    }
+
+    #[test]
+    fn many_small_components() {
+        const N: u32 = 1_000;
+        // a chain of 2-cycles, ending in a component with three cycles
+        let pairs =
+            (0..N).flat_map(|i| [(2 * i, 2 * i + 1), (2 * i + 1, 2 * i)]);
+        let chain = (1..N).map(|i| (2 * i - 1, 2 * i));
+        let last = [(2 * N - 1, 2 * N), (2 * N, 2 * N - 2), (2 * N, 2 * N - 1)];
+        let g = Graph::<(), ()>::from_edges(pairs.chain(chain).chain(last));
+        let indices = |cycles: Vec<Vec<NodeIndex>>| {
+            let cycles = cycles.into_iter().map(|cycle| {
+                cycle.into_iter().map(|n| n.index() as u32).collect()
+            });
+            normalised(cycles.collect())
+        };
+        let mut expected: Vec<Vec<u32>> =
+            (0..N - 1).map(|i| vec![2 * i, 2 * i + 1]).collect();
+        let n = 2 * N;
+        expected.extend([
+            vec![n - 2, n - 1],
+            vec![n - 2, n - 1, n],
+            vec![n - 1, n],
+        ]);
+        assert_eq!(indices(g.cycles()), expected);
+        assert_eq!(indices(g.cycles_iter().collect()), expected);
+
+        // the small components are searched after a large one
+        let g = crate::testing::many_small_components(10_000, 3, 50_000);
+        assert_eq!(g.non_trivial_sccs()[0].len(), 50_000);
+        // five cycles in each complete graph with three nodes
+        assert_eq!(g.count_cycles(), 1 + 5 * 10_000);
+        let mut enumerator = CycleEnumerator::new(&g);
+        assert_eq!(enumerator.all().len(), 1 + 5 * 10_000);
+    }
+
+    #[test]
+    fn has_cycle() {
+        let mut g =
+            Graph::<(), ()>::from_edges([(0, 1), (0, 2), (1, 3), (2, 3)]);
+        assert!(!g.has_cycle());
+        g.add_edge(3.into(), 0.into(), ());
+        assert!(g.has_cycle());
+    }
+
+    #[test]
+    fn first_cycle() {
+        let mut g =
+            Graph::<(), ()>::from_edges([(0, 1), (0, 2), (1, 3), (2, 3)]);
+        assert_eq!(g.first_cycle(), None);
+        g.extend_with_edges([(3, 0), (2, 2)]);
+        let cycle = g.first_cycle().unwrap();
+        assert_eq!(Some(&cycle), g.cycles().first());
+        let next = cycle.iter().cycle().skip(1);
+        for (&from, &to) in cycle.iter().zip(next) {
+            assert!(g.contains_edge(from, to));
+        }
+        let mut nodes = cycle.clone();
+        nodes.sort();
+        nodes.dedup();
+        assert_eq!(nodes.len(), cycle.len());
+    }
+
+    #[test]
+    fn topo_sort_or_cycle() {
+        let mut g = Graph::<(), ()>::from_edges([
+            (4, 2),
+            (2, 1),
+            (2, 3),
+            (1, 0),
+            (3, 0),
+            (5, 5),
+        ]);
+        assert_eq!(g.topo_sort_or_cycle(), Err(vec![5.into()]));
+        let e = g.find_edge(5.into(), 5.into()).unwrap();
+        g.remove_edge(e);
+        let order = g.topo_sort_or_cycle().unwrap();
+        let mut sorted = order.clone();
+        sorted.sort();
+        assert_eq!(sorted, g.node_indices().collect::<Vec<_>>());
+        let pos = |n| order.iter().position(|&m| m == n).unwrap();
+        for e in g.edge_references() {
+            assert!(pos(e.source()) < pos(e.target()));
+        }
+
+        g.extend_with_edges([(0, 4), (3, 5)]);
+        let cycle = g.topo_sort_or_cycle().unwrap_err();
+        assert_eq!(Some(cycle.clone()), g.first_cycle());
+        let next = cycle.iter().cycle().skip(1);
+        for (&from, &to) in cycle.iter().zip(next) {
+            assert!(g.contains_edge(from, to));
+        }
+
+        let mut g = UnGraph::<(), ()>::from_edges([(0, 5)]);
+        let mut order = g.topo_sort_or_cycle().unwrap();
+        order.sort();
+        assert_eq!(order, g.node_indices().collect::<Vec<_>>());
+        g.extend_with_edges([(0, 1), (1, 2), (1, 3), (3, 4)]);
+        assert_eq!(g.topo_sort_or_cycle().map(|o| o.len()), Ok(6));
+        g.add_edge(4.into(), 1.into(), ());
+        let cycle = g.topo_sort_or_cycle().unwrap_err();
+        assert_eq!(Some(cycle), g.first_cycle());
+    }
+
+    #[test]
+    fn has_cycle_of_length_at_most() {
+        let mut g = Graph::<(), ()>::from_edges([
+            (0, 1),
+            (1, 2),
+            (2, 3),
+            (3, 4),
+            (4, 0),
+            (0, 5),
+            (5, 6),
+        ]);
+        assert!(!g.has_cycle_of_length_at_most(0));
+        assert!(!g.has_cycle_of_length_at_most(4));
+        assert!(g.has_cycle_of_length_at_most(5));
+        assert!(g.has_cycle_of_length_at_most(usize::MAX));
+        g.add_edge(6.into(), 6.into(), ());
+        assert!(g.has_cycle_of_length_at_most(1));
+    }
+
+    #[test]
+    fn is_on_any_cycle() {
+        // two components connected by the bridge 2 -> 3
+        let g = Graph::<(), ()>::from_edges([
+            (0, 1),
+            (1, 2),
+            (2, 0),
+            (2, 3),
+            (3, 4),
+            (4, 3),
+            (4, 5),
+            (6, 6),
+        ]);
+        let on_cycle: Vec<_> =
+            g.node_indices().map(|n| g.is_on_any_cycle(n)).collect();
+        assert_eq!(on_cycle, [true, true, true, true, true, false, true]);
+
+        let g = UnGraph::<(), ()>::from_edges([
+            (0, 1),
+            (1, 2),
+            (2, 0),
+            (2, 3),
+            (3, 4),
+            (3, 4),
+            (5, 5),
+        ]);
+        let on_cycle: Vec<_> =
+            g.node_indices().map(|n| g.is_on_any_cycle(n)).collect();
+        assert_eq!(on_cycle, [true, true, true, false, false, true]);
+    }
+
+    #[test]
+    fn edges_on_cycles() {
+        let mut g = Graph::<(), ()>::new();
+        let n: Vec<_> = (0..5).map(|_| g.add_node(())).collect();
+        let in_scc = g.add_edge(n[0], n[1], ());
+        let back = g.add_edge(n[1], n[0], ());
+        let bridge = g.add_edge(n[1], n[2], ());
+        let self_loop = g.add_edge(n[2], n[2], ());
+        g.add_edge(n[2], n[3], ());
+        assert_eq!(g.edges_on_cycles(), [in_scc, back, self_loop]);
+        assert!(!g.edges_on_cycles().contains(&bridge));
+
+        let mut g = UnGraph::<(), ()>::new_undirected();
+        let n: Vec<_> = (0..6).map(|_| g.add_node(())).collect();
+        let triangle = [
+            g.add_edge(n[0], n[1], ()),
+            g.add_edge(n[1], n[2], ()),
+            g.add_edge(n[2], n[0], ()),
+        ];
+        let bridge = g.add_edge(n[2], n[3], ());
+        let parallel = [g.add_edge(n[3], n[4], ()), g.add_edge(n[4], n[3], ())];
+        let self_loop = g.add_edge(n[5], n[5], ());
+        let mut expected = triangle.to_vec();
+        expected.extend(parallel);
+        expected.push(self_loop);
+        assert_eq!(g.edges_on_cycles(), expected);
+        assert!(!g.edges_on_cycles().contains(&bridge));
+    }
+
+    #[test]
+    fn edges_not_on_any_cycle() {
+        let mut g = Graph::<(), ()>::new();
+        let n: Vec<_> = (0..5).map(|_| g.add_node(())).collect();
+        g.add_edge(n[0], n[1], ());
+        let between = g.add_edge(n[1], n[2], ());
+        g.add_edge(n[1], n[0], ());
+        g.add_edge(n[2], n[2], ());
+        let chord = g.add_edge(n[0], n[2], ());
+        let tail = g.add_edge(n[3], n[4], ());
+        assert_eq!(g.edges_not_on_any_cycle(), [between, chord, tail]);
+
+        for g in random_graphs(100, 8, 16) {
+            let on = g.edges_on_cycles();
+            let not_on = g.edges_not_on_any_cycle();
+            assert_eq!(on.len() + not_on.len(), g.edge_count());
+            assert!(not_on.iter().all(|e| !on.contains(e)));
+        }
+
+        let mut g = UnGraph::<(), ()>::new_undirected();
+        let n: Vec<_> = (0..5).map(|_| g.add_node(())).collect();
+        g.add_edge(n[0], n[1], ());
+        g.add_edge(n[1], n[2], ());
+        g.add_edge(n[2], n[0], ());
+        let bridge = g.add_edge(n[2], n[3], ());
+        g.add_edge(n[3], n[4], ());
+        g.add_edge(n[4], n[3], ());
+        assert_eq!(g.edges_not_on_any_cycle(), [bridge]);
+    }
+
+    #[test]
+    fn self_loops() {
+        let g = Graph::<(), ()>::from_edges([
+            (0, 0),
+            (1, 2),
+            (2, 3),
+            (1, 3),
+            (3, 4),
+        ]);
+        assert_eq!(g.cycles(), [[0.into()]]);
+        assert_eq!(g.cycles_iter().collect::<Vec<_>>(), [[0.into()]]);
+        assert_eq!(g.cycles_edges(), [[0.into()]]);
+        let self_loop = CycleSearchOptions::new().max_len(1);
+        assert_eq!(g.cycles_with(&self_loop), [[0.into()]]);
+
+        let mut g = g;
+        g.add_edge(4.into(), 1.into(), ());
+        g.add_edge(3.into(), 3.into(), ());
+        let cycles = normalised(g.cycles());
+        assert_eq!(
+            cycles,
+            [
+                vec![0.into()],
+                vec![1.into(), 2.into(), 3.into(), 4.into()],
+                vec![1.into(), 3.into(), 4.into()],
+                vec![3.into()],
+            ]
+        );
+    }
+
+    #[test]
+    fn triangles() {
+        let k4 = Graph::<(), ()>::from_edges((0..4).flat_map(|i| {
+            (0..4).filter(move |&j| j != i).map(move |j| (i, j))
+        }));
+        let triangles = k4.triangles();
+        // two orientations for each of the four node triples
+        assert_eq!(triangles.len(), 8);
+        for [a, b, c] in triangles {
+            assert!(a < b && a < c);
+        }
+
+        let k4 = UnGraph::<(), ()>::from_edges(
+            (0..4).flat_map(|i| (i + 1..4).map(move |j| (i, j))),
+        );
+        assert_eq!(
+            k4.triangles(),
+            [[0, 1, 2], [0, 1, 3], [0, 2, 3], [1, 2, 3]]
+                .map(|t| t.map(NodeIndex::new))
+        );
+
+        for g in random_graphs(100, 10, 30) {
+            let expected: Vec<_> =
+                g.cycles().into_iter().filter(|c| c.len() == 3).collect();
+            let triangles = g.triangles().into_iter().map(Vec::from).collect();
+            assert_eq!(normalised(triangles), normalised(expected));
+        }
+    }
+
+    #[test]
+    fn cycles_containing_all() {
+        let g = Graph::<(), ()>::from_edges([
+            (0, 1),
+            (1, 2),
+            (2, 0),
+            (1, 0),
+            (2, 3),
+            (3, 2),
+            (3, 4),
+            (4, 5),
+            (5, 4),
+        ]);
+        let mut cycles = g.cycles_containing_all(&[0.into(), 1.into()]);
+        cycles.sort();
+        assert_eq!(
+            cycles,
+            [vec![0.into(), 1.into()], vec![0.into(), 1.into(), 2.into()]]
+        );
+        assert_eq!(
+            g.cycles_containing_all(&[2.into(), 0.into(), 0.into()]),
+            [[2.into(), 0.into(), 1.into()]]
+        );
+        // node 4 is in another strongly connected component
+        assert!(g
+            .cycles_containing_all(&[0.into(), 1.into(), 4.into()])
+            .is_empty());
+        assert_eq!(g.cycles_containing_all(&[]), g.cycles());
+
+        for g in random_graphs(100, 10, 30) {
+            let all = g.cycles();
+            for a in g.node_indices() {
+                for b in g.node_indices() {
+                    let expected: Vec<_> = all
+                        .iter()
+                        .filter(|c| c.contains(&a) && c.contains(&b))
+                        .cloned()
+                        .collect();
+                    assert_eq!(
+                        normalised(g.cycles_containing_all(&[a, b])),
+                        normalised(expected)
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn cycles_avoiding() {
+        let g = Graph::<(), ()>::from_edges([
+            (0, 1),
+            (1, 0),
+            (1, 2),
+            (2, 0),
+            (2, 3),
+            (3, 2),
+        ]);
+        assert_eq!(g.cycles().len(), 3);
+        assert_eq!(
+            normalised(g.cycles_avoiding(&[1.into()])),
+            [[2.into(), 3.into()]]
+        );
+        assert!(g.cycles_avoiding(&[0.into(), 2.into()]).is_empty());
+        assert_eq!(g.cycles_avoiding(&[]), g.cycles());
+
+        for g in random_graphs(100, 10, 30) {
+            let all = g.cycles();
+            for n in g.node_indices() {
+                let expected: Vec<_> =
+                    all.iter().filter(|c| !c.contains(&n)).cloned().collect();
+                assert_eq!(
+                    normalised(g.cycles_avoiding(&[n])),
+                    normalised(expected)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn cycles_through_node() {
+        let mut g = Graph::<(), ()>::from_edges((0..5).flat_map(|i| {
+            (0..5).filter(move |&j| j != i).map(move |j| (i, j))
+        }));
+        g.extend_with_edges([(4, 5), (5, 6), (6, 4), (6, 6), (7, 0)]);
+        let all = g.cycles();
+        for node in g.node_indices() {
+            let cycles = g.cycles_through_node(node);
+            assert!(cycles.iter().all(|c| c[0] == node));
+            let expected: Vec<_> =
+                all.iter().filter(|c| c.contains(&node)).cloned().collect();
+            assert_eq!(normalised(cycles), normalised(expected));
+        }
+        assert!(g.cycles_through_node(7.into()).is_empty());
+        assert_eq!(g.cycles_through_node(5.into()).len(), 1);
+        assert_eq!(
+            g.visit_cycles_through_node(6.into(), |_, c| {
+                if c.len() == 1 {
+                    ControlFlow::Break(c.to_vec())
+                } else {
+                    ControlFlow::Continue(())
+                }
+            }),
+            Some(vec![6.into()])
+        );
+    }
+
+    #[test]
+    fn cycles_created_by_edge() {
+        let mut g = Graph::<(), ()>::from_edges([
+            (0, 1),
+            (1, 2),
+            (1, 3),
+            (2, 4),
+            (3, 4),
+            (4, 5),
+            (5, 5),
+        ]);
+        assert!(g.cycles_created_by_edge(4.into(), 0.into()).is_empty());
+        let before = g.cycles();
+        g.add_edge(4.into(), 0.into(), ());
+        let new = g.cycles_created_by_edge(4.into(), 0.into());
+        assert!(new.iter().all(|c| c[..2] == [4.into(), 0.into()]));
+        let expected: Vec<Vec<NodeIndex>> = [[4, 0, 1, 2], [4, 0, 1, 3]]
+            .iter()
+            .map(|c| c.iter().map(|&n| n.into()).collect())
+            .collect();
+        assert_eq!(normalised(new.clone()), normalised(expected));
+        let mut all = before;
+        all.extend(new);
+        assert_eq!(normalised(all), normalised(g.cycles()));
+        assert_eq!(
+            g.cycles_created_by_edge(5.into(), 5.into()),
+            [[NodeIndex::new(5)]]
+        );
+        assert!(g.cycles_created_by_edge(1.into(), 0.into()).is_empty());
+    }
+
+    #[test]
+    fn cycles_through_edge() {
+        let mut g = Graph::<(), ()>::from_edges([
+            (0, 1),
+            (1, 2),
+            (2, 0),
+            (1, 3),
+            (3, 0),
+            (2, 3),
+        ]);
+        let e = g.find_edge(0.into(), 1.into()).unwrap();
+        assert_eq!(
+            normalised(g.cycles_through_edge(e)),
+            [
+                vec![0.into(), 1.into(), 2.into()],
+                vec![0.into(), 1.into(), 2.into(), 3.into()],
+                vec![0.into(), 1.into(), 3.into()],
+            ]
+        );
+        let e = g.find_edge(2.into(), 0.into()).unwrap();
+        assert_eq!(g.cycles_through_edge(e), [[2.into(), 0.into(), 1.into()]]);
+
+        // only the given edge counts, not a parallel one
+        let parallel = g.add_edge(2.into(), 0.into(), ());
+        assert_eq!(g.cycles_through_edge(e).len(), 1);
+        assert_eq!(g.cycles_through_edge(parallel).len(), 1);
+
+        let self_loop = g.add_edge(3.into(), 3.into(), ());
+        assert_eq!(g.cycles_through_edge(self_loop), [[3.into()]]);
+        let sink = g.add_node(());
+        let outside = g.add_edge(3.into(), sink, ());
+        assert!(g.cycles_through_edge(outside).is_empty());
+
+        let mut g = UnGraph::<(), ()>::from_edges([
+            (0, 1),
+            (1, 2),
+            (2, 0),
+            (2, 3),
+            (3, 0),
+        ]);
+        let e = g.find_edge(0.into(), 2.into()).unwrap();
+        let mut cycles = g.cycles_through_edge(e);
+        cycles.sort();
+        assert_eq!(
+            cycles,
+            [
+                vec![2.into(), 0.into(), 1.into()],
+                vec![2.into(), 0.into(), 3.into()],
+            ]
+        );
+        let parallel = g.add_edge(0.into(), 1.into(), ());
+        assert_eq!(
+            normalised(g.cycles_through_edge(parallel)),
+            [
+                vec![0.into(), 1.into()],
+                vec![0.into(), 1.into(), 2.into()],
+                vec![0.into(), 1.into(), 2.into(), 3.into()],
+            ]
+        );
+    }
+
+    #[test]
+    fn cycles_with_edge_weights_sum_in_range() {
+        let mut g = Graph::<(), f64>::from_edges([
+            (0, 1, 1.),
+            (1, 0, 1.),
+            (1, 2, 2.),
+            (2, 0, 3.),
+            (2, 3, 4.),
+            (3, 2, 4.),
+            (3, 3, 9.),
+            (3, 0, 0.5),
+        ]);
+        // totals: 2, 6, 7.5, 8, 9
+        fn totals(g: &Graph<(), f64>, lo: f64, hi: f64) -> Vec<f64> {
+            let mut totals: Vec<_> = g
+                .cycles_with_edge_weights_sum_in_range(lo, hi, |e| g[e])
+                .into_iter()
+                .map(|(_, total)| total)
+                .collect();
+            totals.sort_by(f64::total_cmp);
+            totals
+        }
+        assert_eq!(totals(&g, 2., 9.), [2., 6., 7.5, 8., 9.]);
+        assert_eq!(totals(&g, 2.5, 7.9), [6., 7.5]);
+        assert_eq!(totals(&g, 7.5, 7.5), [7.5]);
+        assert_eq!(totals(&g, 9., 13.5), [9.]);
+        assert!(totals(&g, f64::NEG_INFINITY, 1.5).is_empty());
+        assert!(totals(&g, 9.5, f64::INFINITY).is_empty());
+        let all = g.cycles_with_total_weight_by(|e| g[e]);
+        for lo in [0., 2., 5.] {
+            for hi in [2., 8., 9., 20.] {
+                let expected: Vec<_> = all
+                    .iter()
+                    .filter(|(_, total)| (lo..=hi).contains(total))
+                    .cloned()
+                    .collect();
+                assert_eq!(
+                    g.cycles_with_edge_weights_sum_in_range(lo, hi, |e| g[e]),
+                    expected
+                );
+            }
+        }
+
+        // with a negative weight, a heavy path can become lighter again
+        let e = g.find_edge(3.into(), 0.into()).unwrap();
+        g[e] = -10.;
+        assert_eq!(totals(&g, -5., 0.), [-3.]);
+    }
+
+    #[test]
+    fn contains_cycle_through_all_edges() {
+        let mut g = Graph::<(), ()>::new();
+        let n: Vec<_> = (0..6).map(|_| g.add_node(())).collect();
+        let a = g.add_edge(n[0], n[1], ());
+        let b = g.add_edge(n[1], n[2], ());
+        let c = g.add_edge(n[2], n[0], ());
+        let d = g.add_edge(n[1], n[3], ());
+        let e = g.add_edge(n[3], n[0], ());
+        let f = g.add_edge(n[3], n[4], ());
+        let self_loop = g.add_edge(n[5], n[5], ());
+        // 0 -> 1 -> 2 -> 0 and 0 -> 1 -> 3 -> 0
+        assert!(g.contains_cycle_through_all_edges(&[a, b, c]));
+        assert!(g.contains_cycle_through_all_edges(&[e, a]));
+        assert!(g.contains_cycle_through_all_edges(&[b, b]));
+        assert!(!g.contains_cycle_through_all_edges(&[b, d]));
+        assert!(!g.contains_cycle_through_all_edges(&[c, e]));
+        // different components
+        assert!(!g.contains_cycle_through_all_edges(&[a, self_loop]));
+        assert!(!g.contains_cycle_through_all_edges(&[f]));
+        assert!(g.contains_cycle_through_all_edges(&[self_loop]));
+        assert!(g.contains_cycle_through_all_edges(&[]));
+
+        for g in random_graphs(100, 6, 14) {
+            let all = g.cycles_edges();
+            let edges: Vec<_> = g.edge_indices().collect();
+            for &e1 in &edges {
+                for &e2 in &edges {
+                    let expected =
+                        all.iter().any(|c| c.contains(&e1) && c.contains(&e2));
+                    assert_eq!(
+                        g.contains_cycle_through_all_edges(&[e1, e2]),
+                        expected
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn neighbor_order() {
+        use crate::NeighborOrder::*;
+
+        // complete graph, with neighbours listed in descending order
+        let mut edges = Vec::new();
+        for i in 0..4 {
+            for j in 0..4 {
+                if i != j {
+                    edges.push((i, j));
+                }
+            }
+        }
+        let g = Graph::<(), ()>::from_edges(edges);
+        let through = CycleSearchOptions::new().through(0.into());
+        let ascending = g.cycles_with(&through.neighbor_order(Ascending));
+        let expected: Vec<Vec<NodeIndex>> = [
+            &[0, 1][..],
+            &[0, 1, 2],
+            &[0, 1, 2, 3],
+            &[0, 1, 3],
+            &[0, 1, 3, 2],
+            &[0, 2],
+            &[0, 2, 1],
+            &[0, 2, 1, 3],
+            &[0, 2, 3],
+            &[0, 2, 3, 1],
+            &[0, 3],
+            &[0, 3, 1],
+            &[0, 3, 1, 2],
+            &[0, 3, 2],
+            &[0, 3, 2, 1],
+        ]
+        .iter()
+        .map(|c| c.iter().map(|&n| NodeIndex::new(n)).collect())
+        .collect();
+        assert_eq!(ascending, expected);
+        assert_ne!(g.cycles_with(&through), expected);
+        let mut descending = g.cycles_with(&through.neighbor_order(Descending));
+        descending.reverse();
+        assert_eq!(descending, expected);
+
+        // starting from the lowest node, the cycles are in
+        // lexicographic order regardless of the graph
+        for g in random_graphs(100, 6, 14) {
+            let through = CycleSearchOptions::new().through(0.into());
+            let ascending = g.cycles_with(&through.neighbor_order(Ascending));
+            assert!(ascending.is_sorted());
+            let mut descending =
+                g.cycles_with(&through.neighbor_order(Descending));
+            descending.reverse();
+            assert_eq!(descending, ascending);
+            let all = CycleSearchOptions::new();
+            assert_eq!(
+                normalised(g.cycles_with(&all.neighbor_order(Ascending))),
+                normalised(g.cycles())
+            );
+        }
+    }
+
+    #[test]
+    fn cycles_with_budget() {
+        // a triangle and a complete graph with eight nodes
+        let mut g = Graph::<(), ()>::from_edges([(0, 1), (1, 2), (2, 0)]);
+        let complete: Vec<_> = (0..8).map(|_| g.add_node(())).collect();
+        for &i in &complete {
+            for &j in &complete {
+                if i != j {
+                    g.add_edge(i, j, ());
+                }
+            }
+        }
+        g.add_edge(2.into(), complete[0], ());
+        let sccs = petgraph::algo::tarjan_scc(&g);
+        let dense = sccs.iter().position(|scc| scc.len() == 8).unwrap();
+
+        let (cycles, skipped) = g.cycles_with_budget(100);
+        assert_eq!(skipped, [dense]);
+        assert_eq!(normalised(cycles), [vec![0.into(), 1.into(), 2.into()]]);
+
+        let (cycles, skipped) = g.cycles_with_budget(u64::MAX);
+        assert!(skipped.is_empty());
+        assert_eq!(cycles, g.cycles());
+
+        let (cycles, skipped) = g.cycles_with_budget(0);
+        assert!(cycles.is_empty());
+        assert_eq!(skipped.len(), 2);
+    }
+
+    #[test]
+    fn visit_cycles_directed_by() {
+        let g = Graph::<(), ()>::from_edges([(0, 1), (1, 2), (0, 2), (2, 3)]);
+        let cycles_directed_by = |flipped: &[usize]| {
+            let mut cycles = Vec::new();
+            g.visit_cycles_directed_by(
+                |e| !flipped.contains(&e.index()),
+                |_, cycle| {
+                    cycles.push(cycle.to_vec());
+                    ControlFlow::<()>::Continue(())
+                },
+            );
+            normalised(cycles)
+        };
+        assert!(cycles_directed_by(&[]).is_empty());
+        assert_eq!(cycles_directed_by(&[2]), [[0.into(), 1.into(), 2.into()]]);
+        assert_eq!(
+            cycles_directed_by(&[0, 1]),
+            [[0.into(), 2.into(), 1.into()]]
+        );
+        assert!(cycles_directed_by(&[0, 1, 2]).is_empty());
+
+        for g in random_graphs(100, 8, 20) {
+            let mut cycles = Vec::new();
+            g.visit_cycles_directed_by(
+                |_| true,
+                |_, cycle| {
+                    cycles.push(cycle.to_vec());
+                    ControlFlow::<()>::Continue(())
+                },
+            );
+            assert_eq!(normalised(cycles), normalised(g.cycles()));
+        }
+
+        let u = UnGraph::<(), ()>::from_edges([(0, 1), (1, 2), (2, 0)]);
+        let mut cycles = Vec::new();
+        u.visit_cycles_directed_by(
+            |e| e.index() != 2,
+            |_, cycle| {
+                cycles.push(cycle.to_vec());
+                ControlFlow::<()>::Continue(())
+            },
+        );
+        assert!(cycles.is_empty());
+    }
+
+    #[test]
+    fn try_visit_cycles() {
+        let g = Graph::<(), ()>::from_edges([(0, 1), (1, 0), (1, 2), (2, 2)]);
+        let mut num_cycles = 0;
+        let outcome = g.try_visit_cycles(|_, _| {
+            num_cycles += 1;
+            ControlFlow::<()>::Continue(())
+        });
+        assert_eq!(outcome, VisitOutcome::Completed);
+        assert!(outcome.is_completed());
+        assert_eq!(num_cycles, 2);
+
+        let outcome = g.try_visit_cycles(|_, cycle| {
+            if cycle.len() == 1 {
+                ControlFlow::Break(cycle[0])
+            } else {
+                ControlFlow::Continue(())
+            }
+        });
+        assert_eq!(outcome, VisitOutcome::Broke(2.into()));
+        assert!(!outcome.is_completed());
+
+        // without cycles, the visitor is never called
+        let dag = Graph::<(), ()>::from_edges([(0, 1)]);
+        let outcome = dag.try_visit_cycles(|_, _| ControlFlow::Break(()));
+        assert_eq!(outcome, VisitOutcome::Completed);
+    }
+
+    #[test]
+    fn visit_cycles_full() {
+        for g in random_graphs(200, 8, 20) {
+            let mut edge_cycles = Vec::new();
+            g.visit_cycles_full(|g, nodes, edges| {
+                assert_eq!(edges.len(), nodes.len());
+                for (i, &e) in edges.iter().enumerate() {
+                    let (source, target) = g.edge_endpoints(e).unwrap();
+                    assert_eq!(source, nodes[i]);
+                    assert_eq!(target, nodes[(i + 1) % nodes.len()]);
+                }
+                edge_cycles.push(edges.to_vec());
+                ControlFlow::<()>::Continue(())
+            });
+            assert_eq!(edge_cycles, g.cycles_edges());
+        }
+
+        let g = Graph::<(), ()>::from_edges([(0, 1), (1, 2), (2, 0), (2, 2)]);
+        let found = g.visit_cycles_full(|_, nodes, edges| {
+            if nodes.len() == 1 {
+                ControlFlow::Break((nodes.to_vec(), edges.to_vec()))
+            } else {
+                ControlFlow::Continue(())
+            }
+        });
+        assert_eq!(found, Some((vec![2.into()], vec![3.into()])));
+    }
+
+    #[test]
+    fn visit_cycles_filtered() {
+        // edge weights mark optional edges
+        let g = Graph::<(), bool>::from_edges([
+            (0, 1, false),
+            (1, 2, false),
+            (2, 0, true),
+            (2, 3, false),
+            (3, 4, false),
+            (4, 3, false),
+        ]);
+        let all_cycles = |edge_ok: &dyn Fn(EdgeIndex) -> bool| {
+            let mut cycles = Vec::new();
+            g.visit_cycles_filtered(edge_ok, |_, cycle| {
+                cycles.push(cycle.to_vec());
+                ControlFlow::<()>::Continue(())
+            });
+            normalised(cycles)
+        };
+        assert_eq!(all_cycles(&|_| true), normalised(g.cycles()));
+        assert_eq!(all_cycles(&|e| !g[e]), [[3.into(), 4.into()]]);
+    }
+
+    #[cfg(feature = "smallvec")]
+    #[test]
+    fn cycles_small() {
+        for g in random_graphs(100, 10, 30) {
+            let cycles = g.cycles();
+            let small = g.cycles_small();
+            assert_eq!(small.len(), cycles.len());
+            for (small, cycle) in small.iter().zip(&cycles) {
+                assert_eq!(small.as_slice(), cycle.as_slice());
+            }
+        }
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn par_cycles() {
+        let mut g = Graph::<(), ()>::new();
+        // 20 complete components with five nodes each
+        for _ in 0..20 {
+            let nodes: Vec<_> = (0..5).map(|_| g.add_node(())).collect();
+            for &i in &nodes {
+                for &j in &nodes {
+                    if i != j {
+                        g.add_edge(i, j, ());
+                    }
+                }
+            }
+        }
+        g.add_edge(0.into(), 5.into(), ());
+        let cycles = g.cycles();
+        assert_eq!(cycles.len(), 20 * 84);
+        assert_eq!(g.par_cycles(), cycles);
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn par_cycles_within_scc() {
+        let mut g = Graph::<(), ()>::from_edges((0..7).flat_map(|i| {
+            (0..7).filter(move |&j| j != i).map(move |j| (i, j))
+        }));
+        g.extend_with_edges([(6, 7), (7, 8), (8, 7), (9, 9)]);
+        let cycles = g.cycles();
+        assert_eq!(cycles.len(), 2365 + 2);
+        assert_eq!(g.par_cycles_within_scc(), cycles);
+
+        for g in random_graphs(100, 10, 30) {
+            assert_eq!(g.par_cycles_within_scc(), g.cycles());
+        }
+
+        // one root per node in a large sparse component
+        let n = 50_000;
+        let ring =
+            Graph::<(), ()>::from_edges((0..n).map(|i| (i, (i + 1) % n)));
+        assert_eq!(ring.par_cycles_within_scc(), ring.cycles());
+    }
+
+    #[test]
+    fn restart_from() {
+        for g in random_graphs(100, 10, 30) {
+            let mut cycles = Vec::new();
+            for component in cyclic_components(&g) {
+                let n = component.len();
+                let mut finder = Finder::new(&g, component);
+                // roots out of order, so that the excluded vertices
+                // both grow and shrink
+                let mut roots =
+                    (1..n).step_by(2).rev().chain((0..n).step_by(2));
+                finder.start_from(roots.next().unwrap());
+                loop {
+                    while let Some((cycle, _)) = finder.next_cycle() {
+                        cycles.push(cycle.to_vec());
+                    }
+                    let Some(s) = roots.next() else {
+                        break;
+                    };
+                    finder.restart_from(s);
+                }
+            }
+            assert_eq!(normalised(cycles), normalised(g.cycles()));
+        }
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn cycles_par_iter() {
+        use rayon::prelude::*;
+
+        let mut g = Graph::<(), ()>::from_edges((0..7).flat_map(|i| {
+            (0..7).filter(move |&j| j != i).map(move |j| (i, j))
+        }));
+        g.extend_with_edges([(6, 7), (7, 8), (8, 7), (9, 9)]);
+        let total: usize = g.cycles().iter().map(Vec::len).sum();
+        let par_total: usize = g.cycles_par_iter().map(|c| c.len()).sum();
+        assert_eq!(par_total, total);
+
+        for g in random_graphs(100, 10, 30) {
+            let mut cycles: Vec<_> = g.cycles_par_iter().collect();
+            cycles.sort();
+            let mut expected = g.cycles();
+            expected.sort();
+            assert_eq!(cycles, expected);
+        }
+    }
+
+    #[cfg(feature = "tokio")]
+    #[tokio::test]
+    async fn stream_cycles() {
+        use std::sync::Arc;
+        use tokio::sync::mpsc;
+
+        let g = Arc::new(Graph::<(), ()>::from_edges((0..5).flat_map(|i| {
+            (0..5).filter(move |&j| j != i).map(move |j| (i, j))
+        })));
+        let (tx, mut rx) = mpsc::channel(4);
+        let search = tokio::task::spawn_blocking({
+            let g = Arc::clone(&g);
+            move || g.stream_cycles(tx)
+        });
+        let mut cycles = Vec::new();
+        while let Some(cycle) = rx.recv().await {
+            cycles.push(cycle);
+        }
+        assert_eq!(search.await.unwrap(), Ok(()));
+        assert_eq!(cycles, g.cycles());
+
+        // stop as soon as the receiver is gone
+        let (tx, mut rx) = mpsc::channel(1);
+        let search = tokio::task::spawn_blocking({
+            let g = Arc::clone(&g);
+            move || g.stream_cycles(tx)
+        });
+        for cycle in &g.cycles()[..3] {
+            assert_eq!(rx.recv().await.as_ref(), Some(cycle));
+        }
+        drop(rx);
+        assert_eq!(search.await.unwrap(), Err(Cancelled));
+    }
+
+    #[test]
+    fn cycle_enumerator() {
+        let mut g = Graph::<(), ()>::from_edges((0..5).flat_map(|i| {
+            (0..5).filter(move |&j| j != i).map(move |j| (i, j))
+        }));
+        g.extend_with_edges([(4, 5), (5, 6), (6, 4), (6, 6), (7, 0)]);
+        let mut enumerator = CycleEnumerator::new(&g);
+        for _ in 0..2 {
+            assert_eq!(enumerator.all(), g.cycles());
+            let short = CycleSearchOptions::new().max_len(3);
+            assert_eq!(enumerator.with_max_len(3), g.cycles_with(&short));
+            for node in g.node_indices() {
+                assert_eq!(
+                    normalised(enumerator.through(node)),
+                    normalised(g.cycles_through_node(node))
+                );
+            }
+        }
+
+        use crate::NeighborOrder::*;
+        for order in [Ascending, Descending, AsIs, Descending, AsIs] {
+            let opts = CycleSearchOptions::new().neighbor_order(order);
+            assert_eq!(enumerator.with_options(&opts), g.cycles_with(&opts));
+            let opts = opts.through(4.into()).max_len(4);
+            assert_eq!(enumerator.with_options(&opts), g.cycles_with(&opts));
+        }
+        for g in random_graphs(100, 6, 14) {
+            let mut enumerator = CycleEnumerator::new(&g);
+            for order in [Ascending, Descending, AsIs] {
+                let opts = CycleSearchOptions::new().neighbor_order(order);
+                assert_eq!(
+                    enumerator.with_options(&opts),
+                    g.cycles_with(&opts)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn cancellation() {
+        let complete = Graph::<(), ()>::from_edges((0..20).flat_map(|i| {
+            (0..20).filter(move |&j| j != i).map(move |j| (i, j))
+        }));
+        let cancel = AtomicBool::new(false);
+        let res = std::thread::scope(|s| {
+            let search = s.spawn(|| {
+                complete.visit_cycles_cancellable(&cancel, |_, _| {
+                    ControlFlow::<()>::Continue(())
+                })
+            });
+            std::thread::sleep(std::time::Duration::from_millis(10));
+            cancel.store(true, Ordering::Relaxed);
+            search.join().unwrap()
+        });
+        assert_eq!(res, Err(Cancelled));
+
+        let triangle = Graph::<(), ()>::from_edges([(0, 1), (1, 2), (2, 0)]);
+        let cancel = AtomicBool::new(false);
+        let res = triangle.visit_cycles_cancellable(&cancel, |_, c| {
+            ControlFlow::Break(c.len())
+        });
+        assert_eq!(res, Ok(Some(3)));
+        let res = triangle.visit_cycles_cancellable(&cancel, |_, _| {
+            ControlFlow::<()>::Continue(())
+        });
+        assert_eq!(res, Ok(None));
+        cancel.store(true, Ordering::Relaxed);
+        let res = triangle.visit_cycles_cancellable(&cancel, |_, _| {
+            ControlFlow::<()>::Continue(())
+        });
+        assert_eq!(res, Err(Cancelled));
+    }
+
+    #[test]
+    fn visit_cycles_bounded() {
+        let complete = Graph::<(), ()>::from_edges((0..20).flat_map(|i| {
+            (0..20).filter(move |&j| j != i).map(move |j| (i, j))
+        }));
+        let mut num_cycles = 0;
+        let res = complete.visit_cycles_bounded(1000, |_, _| {
+            num_cycles += 1;
+            ControlFlow::<()>::Continue(())
+        });
+        assert_eq!(res, Err(BudgetExceeded));
+        assert!(num_cycles < 1000);
+
+        let triangle = Graph::<(), ()>::from_edges([(0, 1), (1, 2), (2, 0)]);
+        let res = triangle
+            .visit_cycles_bounded(100, |_, c| ControlFlow::Break(c.len()));
+        assert_eq!(res, Ok(Some(3)));
+        let res = triangle
+            .visit_cycles_bounded(100, |_, _| ControlFlow::<()>::Continue(()));
+        assert_eq!(res, Ok(None));
+        let res = triangle
+            .visit_cycles_bounded(0, |_, _| ControlFlow::<()>::Continue(()));
+        assert_eq!(res, Err(BudgetExceeded));
+    }
+
+    #[test]
+    fn format_cycle() {
+        let triangle = Graph::<(), ()>::from_edges([(0, 1), (1, 2), (2, 0)]);
+        let cycle = [0.into(), 1.into(), 2.into()];
+        assert_eq!(super::format_cycle(&triangle, &cycle), "0 -> 1 -> 2 -> 0");
+        let labels = ["a", "b", "c"];
+        assert_eq!(
+            format_cycle_with(&cycle, |n| labels[n.index()].to_string()),
+            "a -> b -> c -> a"
+        );
+        assert_eq!(Cycle(vec![2, 0, 1]).to_string(), "2 -> 0 -> 1 -> 2");
+        assert_eq!(Cycle(vec![3]).to_string(), "3 -> 3");
+    }
+
+    #[test]
+    fn cycles_typed() {
+        let mut g = Graph::<(), ()>::from_edges([
+            (0, 1),
+            (1, 2),
+            (2, 0),
+            (1, 0),
+            (3, 3),
+        ]);
+        let cycles = g.cycles_typed();
+        let expected: Vec<Cycle<_>> =
+            g.cycles().into_iter().map(Cycle::from).collect();
+        assert_eq!(cycles, expected);
+        assert!(cycles.iter().all(|c| !c.is_empty()));
+        assert_eq!(cycles.iter().map(Cycle::len).sum::<usize>(), 6);
+
+        let mut triangle = cycles.into_iter().find(|c| c.len() == 3).unwrap();
+        assert!(!triangle.is_self_loop());
+        assert!(triangle.contains(&2.into()));
+        assert!(!triangle.contains(&3.into()));
+        triangle.rotate_to_min();
+        assert_eq!(triangle.nodes(), [0.into(), 1.into(), 2.into()]);
+        let mut rotated = Cycle(vec![2, 0, 1]);
+        rotated.rotate_to_min();
+        assert_eq!(rotated, Cycle(vec![0, 1, 2]));
+
+        let edges: Vec<_> = triangle.edges(&g).collect();
+        let expected: Vec<_> = (0..3).map(EdgeIndex::new).collect();
+        assert_eq!(edges, expected);
+
+        // with parallel edges, the first one is chosen
+        g.add_edge(1.into(), 2.into(), ());
+        let edges: Vec<_> = triangle.edges(&g).collect();
+        let first = g.edges(1.into()).find(|e| e.target() == 2.into());
+        assert_eq!(edges[1], first.unwrap().id());
+        assert!(g.edges_connecting(1.into(), 2.into()).count() == 2);
+
+        let self_loop = Cycle(vec![NodeIndex::new(3)]);
+        assert!(self_loop.is_self_loop());
+        assert_eq!(
+            self_loop.edges(&g).collect::<Vec<_>>(),
+            [EdgeIndex::new(4)]
+        );
+    }
+
+    #[test]
+    fn cycle_to_dot() {
+        let g = Graph::<(), ()>::from_edges([
+            (0, 1),
+            (1, 2),
+            (1, 2),
+            (2, 0),
+            (2, 3),
+        ]);
+        let cycle = [1.into(), 2.into(), 0.into()];
+        assert_eq!(
+            super::cycle_to_dot(&g, &cycle),
+            "digraph {
+    0
+    1
+    2
+    3
+    0 -> 1 [ color = red ]
+    1 -> 2 [ color = red ]
+    1 -> 2
+    2 -> 0 [ color = red ]
+    2 -> 3
+}
+"
+        );
+
+        let g = UnGraph::<(), ()>::from_edges([(0, 1), (1, 0), (1, 1)]);
+        let dot = super::cycle_to_dot(&g, &[0.into(), 1.into()]);
+        assert_eq!(dot.matches("[ color = red ]").count(), 2);
+        let dot = super::cycle_to_dot(&g, &[1.into()]);
+        assert!(dot.contains("1 -- 1 [ color = red ]"));
+    }
+
+    #[test]
+    fn cycles_raw() {
+        let mut g = StableGraph::<(), ()>::from_edges([
+            (0, 1),
+            (1, 2),
+            (2, 0),
+            (2, 3),
+            (3, 2),
+        ]);
+        g.remove_node(0.into());
+        assert_eq!(g.cycles_raw(), [Cycle(vec![3, 2])]);
+    }
+
+    #[test]
+    fn cycles_indices() {
+        let mut g = StableGraph::<(), ()>::from_edges([
+            (0, 1),
+            (1, 2),
+            (2, 0),
+            (2, 3),
+            (3, 2),
+            (4, 4),
+        ]);
+        g.remove_node(1.into());
+        let expected: Vec<Vec<_>> = g
+            .cycles()
+            .iter()
+            .map(|cycle| cycle.iter().map(|&n| g.to_index(n)).collect())
+            .collect();
+        assert_eq!(expected.len(), 2);
+        assert_eq!(g.cycles_indices(), expected);
+
+        let mut first = Vec::new();
+        let res = g.visit_cycles_indices(|_, cycle| {
+            first = cycle.to_vec();
+            ControlFlow::Break(cycle.len())
+        });
+        assert_eq!(res, Some(expected[0].len()));
+        assert_eq!(first, expected[0]);
+    }
+
+    #[test]
+    fn send_cycles_indices() {
+        use std::sync::mpsc;
+
+        let (tx, rx) = mpsc::channel();
+        let handle = std::thread::spawn(move || {
+            let g = Graph::<(), ()>::from_edges([(0, 1), (1, 0), (2, 2)]);
+            tx.send(g.cycles_indices()).unwrap();
+        });
+        let cycles: Vec<Vec<usize>> = rx.recv().unwrap();
+        handle.join().unwrap();
+        assert_eq!(normalised(cycles), [vec![0, 1], vec![2]]);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trip() {
+        let g = Graph::<(), ()>::from_edges([
+            (0, 1),
+            (1, 2),
+            (2, 0),
+            (2, 3),
+            (3, 2),
+        ]);
+        let cycles = g.cycles_raw();
+        let json = serde_json::to_string(&cycles).unwrap();
+        assert_eq!(json, "[[3,2],[2,0,1]]");
+        let read: Vec<Cycle> = serde_json::from_str(&json).unwrap();
+        assert_eq!(read, cycles);
+    }
+
+    #[test]
+    fn cycle_count_capped() {
+        let g = Graph::<(), ()>::from_edges([(0, 1), (1, 0), (1, 2), (2, 0)]);
+        assert_eq!(g.cycle_count_capped(10), CycleCount::Exact(2));
+        assert_eq!(g.cycle_count_capped(3), CycleCount::Exact(2));
+        assert_eq!(g.cycle_count_capped(2), CycleCount::AtLeast(2));
+        assert_eq!(g.cycle_count_capped(0), CycleCount::AtLeast(0));
+
+        let dag = Graph::<(), ()>::from_edges([(0, 1), (1, 2), (0, 2)]);
+        assert_eq!(dag.cycle_count_capped(1), CycleCount::Exact(0));
+
+        // complete graph with 10 nodes, with more than a million cycles
+        let complete = Graph::<(), ()>::from_edges((0..10).flat_map(|i| {
+            (0..10).filter(move |&j| j != i).map(move |j| (i, j))
+        }));
+        assert_eq!(
+            complete.cycle_count_capped(1000),
+            CycleCount::AtLeast(1000)
+        );
+    }
+
+    #[test]
+    fn cycles_grouped_by_length() {
+        let mut g = Graph::<(), ()>::from_edges((0..5).flat_map(|i| {
+            (0..5).filter(move |&j| j != i).map(move |j| (i, j))
+        }));
+        g.extend_with_edges([(4, 5), (5, 6), (6, 4), (7, 7)]);
+        let groups = g.cycles_grouped_by_length();
+        assert_eq!(groups.keys().copied().collect::<Vec<_>>(), [1, 2, 3, 4, 5]);
+        for (&len, cycles) in &groups {
+            assert!(cycles.iter().all(|c| c.len() == len));
+        }
+        let histogram: BTreeMap<_, _> = groups
+            .iter()
+            .map(|(&len, cycles)| (len, cycles.len() as u64))
+            .collect();
+        assert_eq!(histogram, g.cycle_length_histogram());
+        let all = groups.into_values().flatten().collect();
+        assert_eq!(normalised(all), normalised(g.cycles()));
+
+        assert!(Graph::<(), ()>::new().cycles_grouped_by_length().is_empty());
+    }
+
+    #[test]
+    fn cycle_length_histogram() {
+        let complete = Graph::<(), ()>::from_edges((0..4).flat_map(|i| {
+            (0..4).filter(move |&j| j != i).map(move |j| (i, j))
+        }));
+        assert_eq!(
+            complete.cycle_length_histogram(),
+            BTreeMap::from([(2, 6), (3, 8), (4, 6)])
+        );
+
+        let mut g = Graph::<(), ()>::from_edges([
+            (0, 1),
+            (1, 2),
+            (2, 0),
+            (2, 3),
+            (3, 4),
+            (4, 5),
+            (5, 6),
+            (6, 2),
+            (7, 7),
+        ]);
+        assert_eq!(
+            g.cycle_length_histogram(),
+            BTreeMap::from([(1, 1), (3, 1), (5, 1)])
+        );
+        g.add_edge(6.into(), 0.into(), ());
+        assert_eq!(
+            g.cycle_length_histogram(),
+            BTreeMap::from([(1, 1), (3, 1), (5, 1), (7, 1)])
+        );
+
+        let dag = Graph::<(), ()>::from_edges([(0, 1), (1, 2), (0, 2)]);
+        assert!(dag.cycle_length_histogram().is_empty());
+    }
+
+    #[test]
+    fn node_cycle_membership() {
+        let g = Graph::<(), ()>::from_edges([
+            (0, 1),
+            (1, 2),
+            (2, 0),
+            (1, 0),
+            (2, 3),
+            (3, 3),
+            (3, 4),
+        ]);
+        let (cycles, membership) = g.node_cycle_membership();
+        assert_eq!(cycles, g.cycles());
+        assert_eq!(membership.len(), 5);
+        for (n, member_of) in membership.iter().enumerate() {
+            let expected: Vec<_> = (0..cycles.len())
+                .filter(|&i| cycles[i].contains(&NodeIndex::new(n)))
+                .collect();
+            assert_eq!(member_of, &expected);
+        }
+        assert_eq!(membership[0].len(), 2);
+        assert_eq!(membership[3].len(), 1);
+        assert!(membership[4].is_empty());
+    }
+
+    #[test]
+    fn cycle_participation() {
+        // figure eight with centre 0
+        let g = Graph::<(), ()>::from_edges([
+            (0, 1),
+            (1, 2),
+            (2, 0),
+            (0, 3),
+            (3, 4),
+            (4, 0),
+        ]);
+        assert_eq!(g.cycle_participation(), [2, 1, 1, 1, 1]);
+
+        let mut g = StableGraph::<(), ()>::from_edges([(0, 1), (1, 0), (2, 2)]);
+        g.remove_node(0.into());
+        assert_eq!(g.cycle_participation(), [0, 0, 1]);
+    }
+
+    #[test]
+    fn cycles_limited() {
+        let complete = Graph::<(), ()>::from_edges((0..4).flat_map(|i| {
+            (0..4).filter(move |&j| j != i).map(move |j| (i, j))
+        }));
+        let all = complete.cycles();
+        assert_eq!(all.len(), 20);
+        for limit in [0, 1, 7, 19, 20] {
+            assert_eq!(complete.cycles_limited(limit), all[..limit]);
+        }
+        assert_eq!(complete.cycles_limited(50), all);
+    }
+
+    #[test]
+    fn cycles_flat() {
+        let mut g = Graph::<(), ()>::from_edges((0..5).flat_map(|i| {
+            (0..5).filter(move |&j| j != i).map(move |j| (i, j))
+        }));
+        g.extend_with_edges([(5, 5), (6, 7)]);
+        let cycles = g.cycles();
+        let flat = g.cycles_flat();
+        assert_eq!(flat.len(), cycles.len());
+        for (i, cycle) in cycles.iter().enumerate() {
+            assert_eq!(flat.cycle(i), cycle);
+        }
+        assert!(flat.iter().eq(cycles.iter().map(Vec::as_slice)));
+        assert_eq!(flat.nodes(), cycles.concat());
+        assert_eq!(flat.offsets().len(), cycles.len() + 1);
+
+        let flat = Graph::<(), ()>::from_edges([(0, 1)]).cycles_flat();
+        assert!(flat.is_empty());
+        assert_eq!(flat.iter().count(), 0);
+        assert_eq!(flat.offsets(), [0]);
+    }
+
+    #[test]
+    fn cycles_into() {
+        let mut out = vec![vec![NodeIndex::new(7); 20]; 30];
+        let first = Graph::<(), ()>::from_edges((0..5).flat_map(|i| {
+            (0..5).filter(move |&j| j != i).map(move |j| (i, j))
+        }));
+        first.cycles_into(&mut out);
+        assert_eq!(out, first.cycles());
+        // the first 30 cycles reuse the existing allocations
+        assert!(out[..30].iter().all(|c| c.capacity() >= 20));
+
+        let second = Graph::<(), ()>::from_edges([(0, 1), (1, 0), (2, 2)]);
+        second.cycles_into(&mut out);
+        assert_eq!(out, second.cycles());
+
+        first.cycles_into(&mut out);
+        assert_eq!(out, first.cycles());
+
+        Graph::<(), ()>::new().cycles_into(&mut out);
+        assert!(out.is_empty());
+    }
+
+    #[test]
+    fn cycles_up_to() {
+        let mut g = Graph::<(), ()>::from_edges((0..6).flat_map(|i| {
+            (0..6).filter(move |&j| j != i).map(move |j| (i, j))
+        }));
+        g.extend_with_edges((6..20).map(|i| (i, i + 1)));
+        g.extend_with_edges([(20, 6), (6, 6)]);
+        let all = g.cycles();
+        for max_len in [0, 1, 2, 3, 8, 15, 20] {
+            let short: Vec<_> =
+                all.iter().filter(|c| c.len() <= max_len).cloned().collect();
+            for limit in [0, 1, 5, 20, 1000] {
+                let cycles = g.cycles_up_to(limit, max_len);
+                assert!(cycles.len() <= limit);
+                assert!(cycles.iter().all(|c| c.len() <= max_len));
+                assert_eq!(cycles, short[..limit.min(short.len())]);
+            }
+        }
+    }
+
+    #[test]
+    fn same_as_brute_force() {
+        for g in random_graphs(500, 10, 30) {
+            assert_eq!(normalised(g.cycles()), brute_force_cycles(&g));
+        }
+    }
+
+    #[test]
+    fn cycles_with_total_weight() {
+        let mut g = Graph::<(), f64>::from_edges([
+            (0, 1, 1.),
+            (1, 2, 2.),
+            (2, 0, 4.),
+            (2, 3, 8.),
+        ]);
+        let triangle = vec![0.into(), 1.into(), 2.into()];
+        let mut cycles = g.cycles_with_total_weight_by(|e| g[e]);
+        cycle::canonicalise(&mut cycles[0].0, false);
+        assert_eq!(cycles, [(triangle.clone(), 7.)]);
+
+        g.add_edge(1.into(), 2.into(), 16.);
+        g.add_edge(3.into(), 3.into(), 32.);
+        let mut cycles = g.cycles_with_total_weight_by(|e| g[e]);
+        for (cycle, _) in &mut cycles {
+            cycle::canonicalise(cycle, false);
+        }
+        cycles.sort_by(|a, b| a.1.total_cmp(&b.1));
+        assert_eq!(
+            cycles,
+            [
+                (triangle.clone(), 7.),
+                (triangle, 21.),
+                (vec![3.into()], 32.)
+            ]
+        );
+    }
+
+    #[test]
+    fn min_weight_cycle() {
+        let mut g = Graph::<(), f64>::from_edges([
+            (0, 1, 1.),
+            (1, 2, 1.),
+            (2, 0, 1.),
+            (0, 3, 5.),
+            (3, 0, -1.),
+        ]);
+        let e =
+            |from: u32, to: u32| g.find_edge(from.into(), to.into()).unwrap();
+        assert_eq!(
+            g.min_weight_cycle_by(|e| g[e]),
+            Some((vec![e(2, 0), e(0, 1), e(1, 2)], 3.))
+        );
+
+        let cheaper = g.add_edge(1.into(), 2.into(), -1.);
+        let e =
+            |from: u32, to: u32| g.find_edge(from.into(), to.into()).unwrap();
+        assert_eq!(
+            g.min_weight_cycle_by(|e| g[e]),
+            Some((vec![e(2, 0), e(0, 1), cheaper], 1.))
+        );
+
+        assert_eq!(g.min_weight_cycle_by(|e| -g[e]).map(|(_, w)| w), Some(-4.));
+        assert_eq!(g.min_weight_cycle_by(|_| f64::NAN), None);
+
+        let dag = Graph::<(), f64>::from_edges([(0, 1, 1.), (1, 2, 1.)]);
+        assert_eq!(dag.min_weight_cycle_by(|e| dag[e]), None);
+    }
+
+    #[test]
+    fn negative_cycle() {
+        let mut g = Graph::<(), f64>::from_edges([
+            (0, 1, 1.),
+            (1, 2, 1.),
+            (2, 0, -1.),
+            (2, 3, -4.),
+            (3, 4, 1.),
+            (4, 2, 3.5),
+            (4, 5, -10.),
+        ]);
+        assert_eq!(g.find_negative_cycle_by(|e| g[e]), None);
+        assert_eq!(g.find_negative_cycle_by(|_| 0.), None);
+
+        let e = g.find_edge(4.into(), 2.into()).unwrap();
+        g[e] = 3.;
+        assert_eq!(g.find_negative_cycle_by(|e| g[e]), None);
+        g.add_edge(3.into(), 4.into(), -0.5);
+        let cycle = g.find_negative_cycle_by(|e| g[e]).unwrap();
+        assert_eq!(normalised(vec![cycle]), [[2.into(), 3.into(), 4.into()]]);
+
+        let self_loop =
+            Graph::<(), f64>::from_edges([(0, 1, -1.), (1, 1, -1.)]);
+        assert_eq!(
+            self_loop.find_negative_cycle_by(|e| self_loop[e]),
+            Some(vec![1.into()])
+        );
+
+        let mut g = UnGraph::<(), f64>::from_edges([
+            (0, 1, -1.),
+            (1, 2, 1.),
+            (2, 3, 1.),
+            (3, 1, 1.),
+        ]);
+        assert_eq!(g.find_negative_cycle_by(|e| g[e]), None);
+        g.add_edge(2.into(), 0.into(), -1.);
+        let cycle = g.find_negative_cycle_by(|e| g[e]).unwrap();
+        let mut nodes = cycle.clone();
+        nodes.sort();
+        assert_eq!(nodes, [0.into(), 1.into(), 2.into()]);
+    }
+
+    #[test]
+    fn count_cycles_of_length() {
+        for g in random_graphs(300, 8, 25) {
+            let cycles = brute_force_cycles(&g);
+            for len in 0..6 {
+                let count = cycles.iter().filter(|c| c.len() == len).count();
+                assert_eq!(g.count_cycles_of_length(len), count as u64);
+            }
+        }
+
+        let complete = UnGraph::<(), ()>::from_edges(
+            (0..5).flat_map(|i| (0..i).map(move |j| (i, j))),
+        );
+        // choose the nodes and divide by the number of rotations and
+        // orientations
+        assert_eq!(complete.count_cycles_of_length(3), 10);
+        assert_eq!(complete.count_cycles_of_length(4), 5 * 3);
+        assert_eq!(complete.count_cycles_of_length(5), 24 / 2);
+        for len in 0..6 {
+            assert_eq!(
+                complete.count_cycles_of_length(len),
+                complete.cycles().iter().filter(|c| c.len() == len).count()
+                    as u64
+            );
+        }
+    }
+
+    #[test]
+    fn visit_cycles_with_scc() {
+        let g = Graph::<(), ()>::from_edges([
+            (0, 1),
+            (1, 0),
+            (1, 2),
+            (2, 3),
+            (3, 4),
+            (4, 2),
+            (4, 4),
+        ]);
+        let mut cycles = Vec::new();
+        g.visit_cycles_with_scc(|_, scc, cycle| {
+            cycles.push((scc, cycle.to_vec()));
+            ControlFlow::<()>::Continue(())
+        });
+        assert_eq!(
+            cycles,
+            [
+                (0, vec![4.into()]),
+                (0, vec![4.into(), 2.into(), 3.into()]),
+                (1, vec![1.into(), 0.into()]),
+            ]
+        );
+        let sccs = petgraph::algo::tarjan_scc(&g);
+        for (scc, cycle) in cycles {
+            assert!(cycle.iter().all(|n| sccs[scc].contains(n)));
+        }
+    }
+
+    #[test]
+    fn non_trivial_sccs() {
+        let mut g = Graph::<(), ()>::from_edges([
+            (0, 1),
+            (1, 2),
+            (2, 0),
+            (2, 3),
+            (3, 4),
+            (4, 5),
+            (5, 4),
+            (5, 6),
+            (7, 7),
+            (8, 0),
+        ]);
+        g.add_node(());
+        let mut sccs: Vec<_> = g
+            .non_trivial_sccs()
+            .into_iter()
+            .map(|mut scc| {
+                scc.sort_unstable();
+                scc
+            })
+            .collect();
+        sccs.sort_unstable();
+        let expected: Vec<Vec<NodeIndex>> = vec![
+            vec![0.into(), 1.into(), 2.into()],
+            vec![4.into(), 5.into()],
+            vec![7.into()],
+        ];
+        assert_eq!(sccs, expected);
+
+        let dag = Graph::<(), ()>::from_edges([(0, 1), (1, 2), (0, 2)]);
+        assert!(dag.non_trivial_sccs().is_empty());
+
+        let tree = UnGraph::<(), ()>::from_edges([(0, 1), (1, 2)]);
+        assert!(!tree.has_cycle());
+        assert_eq!(tree.non_trivial_sccs().len(), 1);
+    }
+
+    #[test]
+    fn strongly_connected_cycle_components() {
+        // two components with cycles, connected by the edge 2 -> 3,
+        // and the acyclic component {5}
+        let g = Graph::<(), ()>::from_edges([
+            (0, 1),
+            (1, 2),
+            (2, 0),
+            (1, 0),
+            (2, 3),
+            (3, 4),
+            (4, 3),
+            (4, 4),
+            (4, 5),
+        ]);
+        let components = g.strongly_connected_cycle_components();
+        assert_eq!(components.len(), 2);
+        let sccs = petgraph::algo::tarjan_scc(&g);
+        let mut grouped = Vec::new();
+        for (nodes, cycles) in components {
+            let mut sorted = nodes.clone();
+            sorted.sort_unstable();
+            assert!(sccs.iter().any(|scc| {
+                let mut scc = scc.clone();
+                scc.sort_unstable();
+                scc == sorted
+            }));
+            for cycle in &cycles {
+                assert!(cycle.iter().all(|n| nodes.contains(n)));
+            }
+            grouped.push((sorted, normalised(cycles)));
+        }
+        grouped.sort();
+        let idx = |v: &[usize]| -> Vec<NodeIndex> {
+            v.iter().map(|&i| NodeIndex::new(i)).collect()
+        };
+        assert_eq!(
+            grouped,
+            [
+                (idx(&[0, 1, 2]), vec![idx(&[0, 1]), idx(&[0, 1, 2])]),
+                (idx(&[3, 4]), vec![idx(&[3, 4]), idx(&[4])]),
+            ]
+        );
+        let flat = grouped.into_iter().flat_map(|(_, cycles)| cycles);
+        assert_eq!(normalised(flat.collect()), normalised(g.cycles()));
+    }
+
+    #[test]
+    fn cycles_closed() {
+        let triangle = Graph::<(), ()>::from_edges([(0, 1), (1, 2), (2, 0)]);
+        let open = triangle.cycles();
+        let closed = triangle.cycles_closed();
+        assert_eq!(closed.len(), 1);
+        assert_eq!(closed[0][..3], open[0]);
+        assert_eq!(closed[0][3], open[0][0]);
+
+        let self_loop = Graph::<(), ()>::from_edges([(0, 1), (1, 1)]);
+        assert_eq!(self_loop.cycles_closed(), [[1.into(), 1.into()]]);
+    }
+
+    #[test]
+    fn cycles_as_edges_pairs() {
+        let triangle = Graph::<(), ()>::from_edges([(0, 1), (1, 2), (2, 0)]);
+        let mut pairs = triangle.cycles_as_edges_pairs();
+        assert_eq!(pairs.len(), 1);
+        let start = pairs[0].iter().position(|p| p.0 == 0.into()).unwrap();
+        pairs[0].rotate_left(start);
+        assert_eq!(
+            pairs[0],
+            [
+                (0.into(), 1.into()),
+                (1.into(), 2.into()),
+                (2.into(), 0.into())
+            ]
+        );
+
+        let self_loop = Graph::<(), ()>::from_edges([(0, 1), (1, 1)]);
+        assert_eq!(self_loop.cycles_as_edges_pairs(), [[(1.into(), 1.into())]]);
+    }
+
+    #[test]
+    fn visit_cycles_rooted() {
+        for g in random_graphs(100, 10, 30) {
+            let mut cycles = Vec::new();
+            g.visit_cycles_rooted(|_, root, cycle| {
+                assert_eq!(root, cycle[0]);
+                cycles.push(cycle.to_vec());
+                ControlFlow::<()>::Continue(())
+            });
+            assert_eq!(cycles, g.cycles());
+        }
+    }
+
+    #[test]
+    fn visit_cycles_from() {
+        let g = Graph::<(), ()>::from_edges([
+            (0, 1),
+            (1, 0),
+            (1, 2),
+            (2, 1),
+            (2, 0),
+            (3, 3),
+        ]);
+        let from = |starts: &[u32]| {
+            let starts: Vec<_> = starts.iter().map(|&n| n.into()).collect();
+            let mut cycles = Vec::new();
+            g.visit_cycles_from(&starts, |_, cycle| {
+                cycles.push(cycle.to_vec());
+                ControlFlow::<()>::Continue(())
+            });
+            normalised(cycles)
+        };
+        assert_eq!(from(&[]), Vec::<Vec<NodeIndex>>::new());
+        assert_eq!(from(&[3]), [vec![3.into()]]);
+        assert_eq!(from(&[0, 1, 2, 3]), normalised(g.cycles()));
+        // each cycle is only found from one of its nodes
+        let num_found: usize = (0..4).map(|n| from(&[n]).len()).sum();
+        assert_eq!(num_found, 4);
+        assert!((0..3).any(|n| {
+            from(&[n]).len() < g.cycles_through_node(n.into()).len()
+        }));
+
+        for g in random_graphs(100, 10, 30) {
+            let starts: Vec<_> = g.node_indices().step_by(3).collect();
+            let mut expected = Vec::new();
+            g.visit_cycles_rooted(|_, root, cycle| {
+                if starts.contains(&root) {
+                    expected.push(cycle.to_vec());
+                }
+                ControlFlow::<()>::Continue(())
+            });
+            let mut cycles = Vec::new();
+            g.visit_cycles_from(&starts, |_, cycle| {
+                cycles.push(cycle.to_vec());
+                ControlFlow::<()>::Continue(())
+            });
+            assert_eq!(cycles, expected);
+        }
+
+        // every node of a large component as a start
+        let n = 50_000;
+        let ring =
+            Graph::<(), ()>::from_edges((0..n).map(|i| (i, (i + 1) % n)));
+        let starts: Vec<_> = ring.node_indices().collect();
+        let mut num_cycles = 0;
+        ring.visit_cycles_from(&starts, |_, _| {
+            num_cycles += 1;
+            ControlFlow::<()>::Continue(())
+        });
+        assert_eq!(num_cycles, 1);
+    }
+
+    #[test]
+    fn cycles_sorted() {
+        let g = DiGraphMap::<u32, ()>::from_edges([
+            (5, 4),
+            (4, 3),
+            (3, 5),
+            (3, 4),
+            (2, 2),
+            (1, 0),
+            (0, 1),
+            (5, 0),
+            (1, 3),
+        ]);
+        assert_eq!(
+            g.cycles_sorted(),
+            [
+                vec![2],
+                vec![0, 1],
+                vec![3, 4],
+                vec![3, 5, 4],
+                vec![0, 1, 3, 5]
+            ]
+        );
+
+        for g in random_graphs(100, 10, 30) {
+            let sorted = g.cycles_sorted();
+            assert!(sorted
+                .windows(2)
+                .all(|w| (w[0].len(), &w[0]) < (w[1].len(), &w[1])));
+            assert_eq!(sorted.len(), g.cycles().len());
+        }
+    }
+
+    #[test]
+    fn cycles_canonical() {
+        let g = DiGraphMap::<u32, ()>::from_edges([
+            (1, 2),
+            (2, 3),
+            (3, 1),
+            (2, 0),
+            (0, 2),
+        ]);
+        let h = DiGraphMap::<u32, ()>::from_edges([
+            (0, 2),
+            (3, 1),
+            (2, 0),
+            (2, 3),
+            (1, 2),
+        ]);
+        let mut g_cycles = g.cycles_canonical();
+        let mut h_cycles = h.cycles_canonical();
+        g_cycles.sort();
+        h_cycles.sort();
+        assert_eq!(g_cycles, [vec![0, 2], vec![1, 2, 3]]);
+        assert_eq!(g_cycles, h_cycles);
+
+        let g =
+            UnGraphMap::<u32, ()>::from_edges([(4, 2), (2, 3), (3, 1), (1, 4)]);
+        let h =
+            UnGraphMap::<u32, ()>::from_edges([(1, 3), (3, 2), (2, 4), (4, 1)]);
+        assert_eq!(g.cycles_canonical(), [[1, 3, 2, 4]]);
+        assert_eq!(h.cycles_canonical(), [[1, 3, 2, 4]]);
+    }
+
+    #[test]
+    fn cycles_weights() {
+        let mut g = Graph::<&str, ()>::new();
+        let a = g.add_node("a");
+        let b = g.add_node("b");
+        let c = g.add_node("c");
+        g.extend_with_edges([(a, b), (b, c), (c, a), (c, b)]);
+        let cycles = g.cycles();
+        let weights = g.cycles_weights();
+        assert_eq!(cycles.len(), weights.len());
+        for (cycle, weights) in cycles.iter().zip(&weights) {
+            let expected: Vec<_> = cycle.iter().map(|&n| &g[n]).collect();
+            assert_eq!(weights, &expected);
+        }
+        let mut weights = weights;
+        weights.sort();
+        assert_eq!(weights, [vec![&"c", &"a", &"b"], vec![&"c", &"b"]]);
+
+        let mut labels = Vec::new();
+        g.visit_cycles_weights(|_, w| {
+            labels.push(w.iter().copied().copied().collect::<String>());
+            ControlFlow::<()>::Continue(())
+        });
+        labels.sort();
+        assert_eq!(labels, ["cab", "cb"]);
+    }
+
+    #[test]
+    fn chordless_cycles() {
+        let complete = Graph::<(), ()>::from_edges((0..4).flat_map(|i| {
+            (0..4).filter(move |&j| j != i).map(move |j| (i, j))
+        }));
+        let chordless = complete.chordless_cycles();
+        assert_eq!(chordless.len(), 6 + 8);
+        assert!(chordless.iter().all(|c| c.len() <= 3));
+
+        let hole =
+            Graph::<(), ()>::from_edges([(0, 1), (1, 2), (2, 3), (3, 0)]);
+        assert_eq!(hole.chordless_cycles(), hole.cycles());
+        // an edge against the direction of the cycle is also a chord
+        let mut g = hole.clone();
+        g.add_edge(2.into(), 0.into(), ());
+        assert_eq!(
+            normalised(g.chordless_cycles()),
+            [vec![0.into(), 1.into(), 2.into()]]
+        );
+        // parallel edges and edges between neighbours are not chords
+        let mut g = hole.clone();
+        g.extend_with_edges([(0, 1), (1, 0), (2, 2)]);
+        assert_eq!(g.chordless_cycles().len(), 3);
+
+        let hole =
+            UnGraph::<(), ()>::from_edges([(0, 1), (1, 2), (2, 3), (3, 0)]);
+        assert_eq!(hole.chordless_cycles().len(), 1);
+        let mut g = hole.clone();
+        g.add_edge(1.into(), 3.into(), ());
+        assert_eq!(g.chordless_cycles().len(), 2);
+    }
+
+    #[test]
+    fn undirected_cycles() {
+        // no directed cycles apart from the self-loop
+        let mut g = Graph::<(), ()>::from_edges([
+            (0, 1),
+            (0, 2),
+            (1, 3),
+            (2, 3),
+            (3, 4),
+            (5, 5),
+        ]);
+        assert_eq!(g.cycles(), [vec![5.into()]]);
+        assert_eq!(
+            normalised(g.undirected_cycles()),
+            [vec![0.into(), 1.into(), 3.into(), 2.into()], vec![5.into()]]
+        );
+        // antiparallel edges don't form a cycle
+        g.add_edge(4.into(), 3.into(), ());
+        g.add_edge(1.into(), 0.into(), ());
+        assert_eq!(g.undirected_cycles().len(), 2);
+
+        for g in random_graphs(100, 10, 30) {
+            let mut undirected = UnGraph::<(), ()>::new_undirected();
+            for _ in g.node_indices() {
+                undirected.add_node(());
+            }
+            for e in g.edge_references() {
+                if !undirected.contains_edge(e.source(), e.target()) {
+                    undirected.add_edge(e.source(), e.target(), ());
+                }
+            }
+            let mut cycles = g.undirected_cycles();
+            for cycle in &mut cycles {
+                cycle::canonicalise(cycle, true);
+            }
+            cycles.sort();
+            let mut expected = undirected.cycles_canonical();
+            expected.sort();
+            assert_eq!(cycles, expected);
+        }
+    }
+
+    #[test]
+    fn reversed_cycles() {
+        let triangle = Graph::<(), ()>::from_edges([(0, 1), (1, 2), (2, 0)]);
+        assert_eq!(
+            normalised(triangle.reversed_cycles()),
+            [[0.into(), 2.into(), 1.into()]]
+        );
+
+        for g in random_graphs(100, 8, 20) {
+            let mut reversed = g.reversed_cycles();
+            for cycle in &mut reversed {
+                cycle.reverse();
+            }
+            assert_eq!(normalised(reversed), normalised(g.cycles()));
+        }
+    }
+
+    #[test]
+    fn combined_options() {
+        let complete = Graph::<(), ()>::from_edges((0..5).flat_map(|i| {
+            (0..5).filter(move |&j| j != i).map(move |j| (i, j))
+        }));
+        let mut g = complete.clone();
+        g.extend_with_edges([(0, 0), (1, 1)]);
+        let all = g.cycles();
+
+        assert_eq!(g.cycles_with(&CycleSearchOptions::new()), all);
+
+        let opts = CycleSearchOptions::new().max_len(3).through(2.into());
+        let expected: Vec<_> = all
+            .iter()
+            .filter(|c| c.len() <= 3 && c.contains(&2.into()))
+            .cloned()
+            .collect();
+        let cycles = g.cycles_with(&opts);
+        assert!(cycles.iter().all(|c| c[0] == 2.into()));
+        assert_eq!(normalised(cycles), normalised(expected));
+
+        let opts = CycleSearchOptions::new().min_len(4).limit(10);
+        let expected: Vec<_> = all
+            .iter()
+            .filter(|c| c.len() >= 4)
+            .take(10)
+            .cloned()
+            .collect();
+        assert_eq!(g.cycles_with(&opts), expected);
+
+        let opts = CycleSearchOptions::new()
+            .skip_self_loops(true)
+            .through(0.into());
+        assert_eq!(
+            g.cycles_with(&opts),
+            complete.cycles_through_node(0.into())
+        );
+        let opts = CycleSearchOptions::new().max_len(1).through(1.into());
+        assert_eq!(g.cycles_with(&opts), [[1.into()]]);
+        let opts = opts.skip_self_loops(true);
+        assert!(g.cycles_with(&opts).is_empty());
+        let opts = CycleSearchOptions::new().through(5.into());
+        assert!(g.cycles_with(&opts).is_empty());
+    }
+
+    #[test]
+    fn visit_cycle_counts() {
+        let complete = Graph::<(), ()>::from_edges((0..5).flat_map(|i| {
+            (0..5).filter(move |&j| j != i).map(move |j| (i, j))
+        }));
+        let mut reports = Vec::new();
+        let last = complete.visit_cycle_counts(10, |p| reports.push(p.clone()));
+        assert_eq!(reports.len(), 84 / 10 + 1);
+        for (n, report) in reports[..8].iter().enumerate() {
+            assert_eq!(report.total(), 10 * (n as u64 + 1));
+            assert_eq!(
+                report.by_length().values().sum::<u64>(),
+                report.total()
+            );
+            assert!(!report.is_finished());
+        }
+        assert_eq!(reports.last(), Some(&last));
+        assert!(last.is_finished());
+        assert_eq!(last.total(), complete.count_cycles() as u64);
+        assert_eq!(last.by_length(), &complete.cycle_length_histogram());
+
+        let mut calls = 0;
+        let last = complete.visit_cycle_counts(0, |_| calls += 1);
+        assert_eq!(calls, 1);
+        assert_eq!(last.total(), 84);
+    }
+
+    #[test]
+    fn count_cycles() {
+        let triangle = Graph::<(), ()>::from_edges([(0, 1), (1, 2), (2, 0)]);
+        let figure_eight = Graph::<(), ()>::from_edges([
+            (0, 1),
+            (1, 2),
+            (2, 0),
+            (0, 3),
+            (3, 4),
+            (4, 0),
+        ]);
+        let complete = Graph::<(), ()>::from_edges((0..3).flat_map(|i| {
+            (0..3).filter(move |&j| j != i).map(move |j| (i, j))
+        }));
+        let dag = Graph::<(), ()>::from_edges([(0, 1), (1, 2), (0, 2)]);
+        for (g, count) in
+            [(triangle, 1), (figure_eight, 2), (complete, 5), (dag, 0)]
+        {
+            assert_eq!(g.count_cycles(), count);
+            assert_eq!(g.count_cycles(), g.cycles().len());
+        }
+    }
+
+    #[test]
+    fn parallel_edges() {
+        let mut g = Graph::<(), ()>::new();
+        let a = g.add_node(());
+        let b = g.add_node(());
+        let c = g.add_node(());
+        let ab1 = g.add_edge(a, b, ());
+        let ab2 = g.add_edge(a, b, ());
+        let ba = g.add_edge(b, a, ());
+        let bc = g.add_edge(b, c, ());
+        let ca = g.add_edge(c, a, ());
+        let mut cycles = g.cycles_edges();
+        for cycle in &mut cycles {
+            let start = cycle.iter().position(|&e| e == ba || e == bc).unwrap();
+            cycle.rotate_left(start);
+        }
+        cycles.sort();
+        assert_eq!(
+            cycles,
+            [
+                vec![ba, ab1],
+                vec![ba, ab2],
+                vec![bc, ca, ab1],
+                vec![bc, ca, ab2]
+            ]
+        );
+    }
+
+    #[test]
+    fn mutual_edges() {
+        let edges = [
+            (0, 1),
+            (1, 0),
+            (1, 2),
+            (3, 2),
+            (2, 3),
+            (4, 5),
+            (5, 4),
+            (4, 5),
+            (3, 6),
+            (6, 7),
+            (7, 8),
+            (8, 6),
+            (8, 7),
+            (9, 0),
+        ];
+        let cycles = [
+            vec![0, 1],
+            vec![2, 3],
+            vec![4, 5],
+            vec![6, 7, 8],
+            vec![7, 8],
+        ];
+        // vary the order of the nodes and edges, and thereby the
+        // order of the components
+        for relabel in [|n: u32| n, |n: u32| (3 * n + 1) % 10] {
+            for rotation in 0..edges.len() {
+                let mut edges = edges.map(|(a, b)| (relabel(a), relabel(b)));
+                edges.rotate_left(rotation);
+                let g = Graph::<(), ()>::from_edges(edges);
+                let expected = normalised(
+                    cycles
+                        .iter()
+                        .map(|c| c.iter().map(|&n| relabel(n).into()).collect())
+                        .collect(),
+                );
+                assert_eq!(normalised(g.cycles()), expected);
+                assert_eq!(g.count_cycles_of_length(2), 4);
+            }
+        }
+    }
+
+    #[test]
+    fn cycle_lengths() {
+        let complete = Graph::<(), ()>::from_edges((0..5).flat_map(|i| {
+            (0..5).filter(move |&j| j != i).map(move |j| (i, j))
+        }));
+        let figure_eight = Graph::<(), ()>::from_edges([
+            (0, 1),
+            (1, 2),
+            (2, 0),
+            (0, 3),
+            (3, 4),
+            (4, 5),
+            (5, 6),
+            (6, 0),
+            (4, 2),
+        ]);
+        for g in [complete, figure_eight] {
+            let cycles = g.cycles();
+            for min_len in 0..8 {
+                for max_len in 0..8 {
+                    let opts = CycleSearchOptions::new()
+                        .min_len(min_len)
+                        .max_len(max_len);
+                    let expected: Vec<_> = cycles
+                        .iter()
+                        .filter(|c| (min_len..=max_len).contains(&c.len()))
+                        .cloned()
+                        .collect();
+                    assert_eq!(g.cycles_with(&opts), expected);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn girth() {
+        let triangle = Graph::<(), ()>::from_edges([(0, 1), (1, 2), (2, 0)]);
+        assert_eq!(triangle.girth(), Some(3));
+
+        let mut square =
+            Graph::<(), ()>::from_edges([(0, 1), (1, 2), (2, 3), (3, 0)]);
+        assert_eq!(square.girth(), Some(4));
+        square.add_edge(1.into(), 0.into(), ());
+        assert_eq!(square.girth(), Some(2));
+        square.add_edge(2.into(), 2.into(), ());
+        assert_eq!(square.girth(), Some(1));
+
+        let dag = Graph::<(), ()>::from_edges([(0, 1), (1, 2), (0, 2)]);
+        assert_eq!(dag.girth(), None);
+
+        let long_and_short = Graph::<(), ()>::from_edges([
+            (0, 1),
+            (1, 2),
+            (2, 3),
+            (3, 4),
+            (4, 0),
+            (2, 5),
+            (5, 6),
+            (6, 2),
+        ]);
+        assert_eq!(long_and_short.girth(), Some(3));
+    }
+
+    #[test]
+    fn shortest_cycle() {
+        let mut g = Graph::<(), ()>::from_edges(
+            (1..10).map(|i| (i - 1, i)).chain([(9, 0)]),
+        );
+        assert_eq!(g.shortest_cycle().map(|c| c.len()), Some(10));
+        g.extend_with_edges([(6, 10), (10, 11), (11, 6)]);
+        assert_eq!(
+            g.shortest_cycle(),
+            Some(vec![6.into(), 10.into(), 11.into()])
+        );
+        g.add_edge(11.into(), 5.into(), ());
+        assert_eq!(g.shortest_cycle().map(|c| c.len()), Some(3));
+
+        let dag = Graph::<(), ()>::from_edges([(0, 1), (1, 2), (0, 2)]);
+        assert_eq!(dag.shortest_cycle(), None);
+
+        let self_loop = Graph::<(), ()>::from_edges([(0, 1), (1, 1)]);
+        assert_eq!(self_loop.shortest_cycle(), Some(vec![1.into()]));
+    }
+
+    #[test]
+    fn longest_cycle() {
+        let g = Graph::<(), ()>::from_edges([
+            (0, 1),
+            (1, 0),
+            (2, 3),
+            (3, 4),
+            (4, 5),
+            (5, 2),
+            (4, 2),
+        ]);
+        let first = g.visit_cycles(|_, c| ControlFlow::Break(c.to_vec()));
+        assert_eq!(first.map(|c| c.len()), Some(2));
+        let mut longest = g.longest_cycle().unwrap();
+        let start = longest.iter().position(|&n| n == 2.into()).unwrap();
+        longest.rotate_left(start);
+        assert_eq!(longest, [2.into(), 3.into(), 4.into(), 5.into()]);
+
+        let dag = Graph::<(), ()>::from_edges([(0, 1), (1, 2), (0, 2)]);
+        assert_eq!(dag.longest_cycle(), None);
+    }
+
+    #[test]
+    fn fundamental_cycle_basis() {
+        let two_loops = UnGraph::<(), ()>::from_edges([
+            (0, 1),
+            (1, 2),
+            (2, 0),
+            (2, 3),
+            (3, 4),
+            (4, 5),
+            (5, 3),
+        ]);
+        assert_eq!(
+            two_loops.fundamental_cycle_basis(),
+            [
+                [1.into(), 0.into(), 2.into()],
+                [4.into(), 3.into(), 5.into()]
+            ]
+        );
+
+        // edge directions are ignored
+        let dag = Graph::<(), ()>::from_edges([(0, 1), (1, 2), (0, 2), (2, 2)]);
+        assert_eq!(dag.fundamental_cycle_basis().len(), 2);
+        assert_eq!(dag.fundamental_cycle_basis()[0], [2.into()]);
+
+        let tree = Graph::<(), ()>::from_edges([(0, 1), (0, 2), (2, 3)]);
+        assert!(tree.fundamental_cycle_basis().is_empty());
+    }
+
+    #[test]
+    fn undirected() {
+        let mut triangle =
+            UnGraph::<(), ()>::from_edges([(0, 1), (1, 2), (2, 0)]);
+        let cycles = triangle.cycles();
+        assert_eq!(cycles.len(), 1);
+        let mut nodes = cycles[0].clone();
+        nodes.sort();
+        assert_eq!(nodes, [0.into(), 1.into(), 2.into()]);
+        assert_eq!(triangle.cycles_edges().len(), 1);
+        assert_eq!(triangle.girth(), Some(3));
+
+        let square =
+            UnGraphMap::<u8, ()>::from_edges([(0, 1), (1, 2), (2, 3), (3, 0)]);
+        let cycles = normalised(square.cycles());
+        assert!(cycles == [[0, 1, 2, 3]] || cycles == [[0, 3, 2, 1]]);
+        assert_eq!(square.shortest_cycle().map(|c| c.len()), Some(4));
+
+        // with a chord
+        let mut square = square.into_graph::<u32>();
+        square.add_edge(0.into(), 2.into(), ());
+        assert_eq!(square.count_cycles(), 3);
+        assert_eq!(square.girth(), Some(3));
+
+        // parallel edges only form cycles in terms of edges
+        let e = triangle.add_edge(0.into(), 1.into(), ());
+        assert_eq!(triangle.count_cycles(), 1);
+        let cycles = triangle.cycles_edges();
+        assert_eq!(cycles.len(), 3);
+        assert!(
+            cycles.contains(&vec![0.into(), e])
+                || cycles.contains(&vec![e, 0.into()])
+        );
+        assert_eq!(triangle.girth(), Some(3));
+
+        triangle.add_edge(2.into(), 2.into(), ());
+        assert_eq!(triangle.count_cycles(), 2);
+        assert_eq!(triangle.cycles_edges().len(), 4);
+        assert_eq!(
+            sorted_edges(triangle.cycles_edges()),
+            brute_force_cycles_edges(&triangle)
+        );
+        assert_eq!(triangle.shortest_cycle(), Some(vec![2.into()]));
+    }
+
+    // sort the edges in each cycle and the cycles
+    fn sorted_edges(mut cycles: Vec<Vec<EdgeIndex>>) -> Vec<Vec<EdgeIndex>> {
+        for cycle in &mut cycles {
+            cycle.sort();
+        }
+        cycles.sort();
+        cycles
+    }
+
+    proptest::proptest! {
+        #[test]
+        fn prop_same_as_brute_force(g in arb_graph()) {
+            let expected = brute_force_cycles(&g);
+            proptest::prop_assert_eq!(normalised(g.cycles()), expected.clone());
+            proptest::prop_assert_eq!(
+                normalised(g.cycles_iter().collect()),
+                expected.clone()
+            );
+            proptest::prop_assert_eq!(g.count_cycles(), expected.len());
+        }
+
+        #[test]
+        fn prop_len_bounds_same_as_brute_force(
+            g in arb_graph(),
+            min_len in 0..5usize,
+            max_len in 0..5usize,
+        ) {
+            let options =
+                CycleSearchOptions::new().min_len(min_len).max_len(max_len);
+            let mut expected = brute_force_cycles(&g);
+            expected.retain(|c| (min_len..=max_len).contains(&c.len()));
+            proptest::prop_assert_eq!(
+                normalised(g.cycles_with(&options)),
+                expected
+            );
+        }
+
+        #[test]
+        fn prop_edges_same_as_brute_force(g in arb_graph()) {
+            proptest::prop_assert_eq!(
+                sorted_edges(g.cycles_edges()),
+                brute_force_cycles_edges(&g)
+            );
+        }
+
+        #[test]
+        fn prop_undirected_same_as_brute_force(g in arb_ungraph()) {
+            let mut cycles = g.cycles_canonical();
+            cycles.sort();
+            proptest::prop_assert_eq!(cycles, brute_force_cycles(&g));
+            proptest::prop_assert_eq!(
+                sorted_edges(g.cycles_edges()),
+                brute_force_cycles_edges(&g)
+            );
+        }
+    }
 }
@@ -0,0 +1,61 @@
+use alloc::{vec, vec::Vec};
+
+use petgraph::visit::{EdgeRef, IntoEdges, IntoNodeIdentifiers, NodeIndexable};
+
+/// Find a cycle with negative total weight in a directed graph
+///
+/// This uses the Bellman-Ford algorithm, starting from all nodes at
+/// once. The run time is O(VE).
+pub(crate) fn negative_cycle<G, W>(
+    graph: G,
+    mut weight: W,
+) -> Option<Vec<G::NodeId>>
+where
+    G: IntoEdges + IntoNodeIdentifiers + NodeIndexable,
+    W: FnMut(G::EdgeId) -> f64,
+{
+    // source, target, and weight of each edge
+    let edges: Vec<_> = graph
+        .node_identifiers()
+        .flat_map(|n| graph.edges(n))
+        .map(|e| {
+            (
+                graph.to_index(e.source()),
+                graph.to_index(e.target()),
+                weight(e.id()),
+            )
+        })
+        .collect();
+    if edges.is_empty() {
+        return None;
+    }
+    let num_nodes = graph.node_identifiers().count();
+    let mut dist = vec![0.; graph.node_bound()];
+    let mut pred = vec![usize::MAX; graph.node_bound()];
+    let mut start = 0;
+    for _ in 0..num_nodes {
+        let mut last_relaxed = None;
+        for &(from, to, weight) in &edges {
+            if dist[from] + weight < dist[to] {
+                dist[to] = dist[from] + weight;
+                pred[to] = from;
+                last_relaxed = Some(to);
+            }
+        }
+        // if nothing changes, there is no negative cycle
+        start = last_relaxed?;
+    }
+    // After `num_nodes` rounds, following the predecessors from a
+    // node that is still being relaxed leads into a negative cycle.
+    for _ in 0..num_nodes {
+        start = pred[start];
+    }
+    let mut cycle = vec![graph.from_index(start)];
+    let mut n = pred[start];
+    while n != start {
+        cycle.push(graph.from_index(n));
+        n = pred[n];
+    }
+    cycle.reverse();
+    Some(cycle)
+}
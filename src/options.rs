@@ -0,0 +1,129 @@
+/// Options for the search for cycles
+///
+/// The default options find all cycles. The type parameter `N` is
+/// the node identifier of the graph.
+///
+/// # Example
+///
+/// ```rust
+/// use graph_cycles::{CycleSearchOptions, Cycles};
+/// use petgraph::graph::Graph;
+///
+/// let g = Graph::<(), ()>::from_edges([(0, 1), (1, 0), (1, 2), (2, 0)]);
+///
+/// let opts = CycleSearchOptions::new().min_len(3);
+/// let cycles = g.cycles_with(&opts);
+/// assert_eq!(cycles.len(), 1);
+/// assert_eq!(cycles[0].len(), 3);
+///
+/// let opts = CycleSearchOptions::new().max_len(2).through(0.into());
+/// let cycles = g.cycles_with(&opts);
+/// assert_eq!(cycles, [[0.into(), 1.into()]]);
+/// ```
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct CycleSearchOptions<N> {
+    pub(crate) min_len: usize,
+    pub(crate) max_len: usize,
+    pub(crate) limit: usize,
+    pub(crate) through: Option<N>,
+    pub(crate) skip_self_loops: bool,
+    pub(crate) neighbor_order: NeighborOrder,
+}
+
+/// Order in which the neighbours of each node are explored
+///
+/// This determines the order in which cycles through the same first
+/// node are found. With [AsIs](NeighborOrder::AsIs), the neighbours
+/// are explored in the order in which the graph lists them. The other
+/// variants sort them by their [NodeIndexable](petgraph::visit::NodeIndexable)
+/// index, which makes the order independent of how the graph was
+/// built.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub enum NeighborOrder {
+    /// Keep the order of the graph
+    #[default]
+    AsIs,
+    /// Explore neighbours with lower indices first
+    Ascending,
+    /// Explore neighbours with higher indices first
+    Descending,
+}
+
+impl<N> CycleSearchOptions<N> {
+    /// Default options, finding all cycles
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Only find cycles with at least `len` nodes
+    pub fn min_len(mut self, len: usize) -> Self {
+        self.min_len = len;
+        self
+    }
+
+    /// Only find cycles with at most `len` nodes
+    ///
+    /// In contrast to [min_len](Self::min_len), this prunes the search
+    /// and can therefore be considerably faster than filtering the
+    /// cycles afterwards.
+    pub fn max_len(mut self, len: usize) -> Self {
+        self.max_len = len;
+        self
+    }
+
+    /// Stop after finding `limit` cycles
+    ///
+    /// Which cycles are found first depends on the order of the nodes
+    /// in the graph.
+    pub fn limit(mut self, limit: usize) -> Self {
+        self.limit = limit;
+        self
+    }
+
+    /// Only find cycles through `node`
+    ///
+    /// The search only starts from `node`, so this is much faster
+    /// than filtering the cycles afterwards. Each cycle starts with
+    /// `node`.
+    pub fn through(mut self, node: N) -> Self {
+        self.through = Some(node);
+        self
+    }
+
+    /// Whether to skip cycles formed by a single self-loop
+    pub fn skip_self_loops(mut self, skip: bool) -> Self {
+        self.skip_self_loops = skip;
+        self
+    }
+
+    /// Explore the neighbours of each node in the given `order`
+    ///
+    /// This does not change which cycles are found, only their order.
+    pub fn neighbor_order(mut self, order: NeighborOrder) -> Self {
+        self.neighbor_order = order;
+        self
+    }
+
+    // minimum and maximum number of nodes in a cycle
+    pub(crate) fn len_bounds(&self) -> (usize, usize) {
+        let min_len = if self.skip_self_loops {
+            self.min_len.max(2)
+        } else {
+            self.min_len
+        };
+        (min_len, self.max_len)
+    }
+}
+
+impl<N> Default for CycleSearchOptions<N> {
+    fn default() -> Self {
+        Self {
+            min_len: 0,
+            max_len: usize::MAX,
+            limit: usize::MAX,
+            through: None,
+            skip_self_loops: false,
+            neighbor_order: NeighborOrder::AsIs,
+        }
+    }
+}
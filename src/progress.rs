@@ -0,0 +1,65 @@
+use alloc::collections::BTreeMap;
+
+/// Running tally of the cycles found so far
+///
+/// Passed to the callback of
+/// [visit_cycle_counts](crate::Cycles::visit_cycle_counts).
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct CycleProgress {
+    pub(crate) total: u64,
+    pub(crate) by_length: BTreeMap<usize, u64>,
+    pub(crate) finished: bool,
+}
+
+impl CycleProgress {
+    /// The number of cycles found so far
+    pub fn total(&self) -> u64 {
+        self.total
+    }
+
+    /// The number of cycles found so far for each cycle length
+    ///
+    /// Lengths without any cycles are omitted.
+    pub fn by_length(&self) -> &BTreeMap<usize, u64> {
+        &self.by_length
+    }
+
+    /// Whether the search is finished, i.e. all cycles were found
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    pub(crate) fn add(&mut self, len: usize) {
+        self.total += 1;
+        *self.by_length.entry(len).or_default() += 1;
+    }
+}
+
+/// Number of cycles, possibly only a lower bound
+///
+/// Returned by [cycle_count_capped](crate::Cycles::cycle_count_capped).
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum CycleCount {
+    /// There are exactly this many cycles
+    Exact(u64),
+    /// There are at least this many cycles
+    AtLeast(u64),
+}
+
+/// How a visit of all cycles ended
+///
+/// Returned by [try_visit_cycles](crate::Cycles::try_visit_cycles).
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum VisitOutcome<B> {
+    /// All cycles were visited
+    Completed,
+    /// The visitor stopped the search with `ControlFlow::Break(b)`
+    Broke(B),
+}
+
+impl<B> VisitOutcome<B> {
+    /// Whether all cycles were visited
+    pub fn is_completed(&self) -> bool {
+        matches!(self, Self::Completed)
+    }
+}
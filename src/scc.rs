@@ -0,0 +1,177 @@
+use alloc::{vec, vec::Vec};
+
+use petgraph::visit::{IntoNeighbors, IntoNodeIdentifiers, NodeIndexable};
+
+/// Compute the strongly connected components of a graph
+///
+/// This is a non-recursive version of [petgraph::algo::tarjan_scc],
+/// which returns the components in the same order. Avoiding recursion
+/// means that we don't overflow the stack for long paths.
+pub(crate) fn tarjan_scc<G>(g: G) -> Vec<Vec<G::NodeId>>
+where
+    G: IntoNodeIdentifiers + IntoNeighbors + NodeIndexable,
+{
+    let mut sccs = Vec::new();
+    // `rootindex` of zero means that the node has not been visited yet
+    let mut rootindex = vec![0; g.node_bound()];
+    let mut index = 1;
+    let mut componentcount = usize::MAX;
+    let mut stack = Vec::new();
+    let mut frames: Vec<Frame<G>> = Vec::new();
+
+    for n in g.node_identifiers() {
+        if rootindex[g.to_index(n)] != 0 {
+            continue;
+        }
+        rootindex[g.to_index(n)] = index;
+        index += 1;
+        frames.push(Frame::new(g, n));
+        while let Some(frame) = frames.last_mut() {
+            let v = g.to_index(frame.v);
+            if let Some(w) = frame.neighbors.next() {
+                if rootindex[g.to_index(w)] == 0 {
+                    // descend into `w`
+                    rootindex[g.to_index(w)] = index;
+                    index += 1;
+                    frames.push(Frame::new(g, w));
+                    continue;
+                }
+                if rootindex[g.to_index(w)] < rootindex[v] {
+                    rootindex[v] = rootindex[g.to_index(w)];
+                    frame.is_local_root = false;
+                }
+                continue;
+            }
+
+            // all neighbours visited
+            let frame = frames.pop().unwrap();
+            if frame.is_local_root {
+                let mut indexadjustment = 1;
+                let start = stack
+                    .iter()
+                    .rposition(|&w| {
+                        if rootindex[v] > rootindex[g.to_index(w)] {
+                            true
+                        } else {
+                            rootindex[g.to_index(w)] = componentcount;
+                            indexadjustment += 1;
+                            false
+                        }
+                    })
+                    .map(|x| x + 1)
+                    .unwrap_or_default();
+                rootindex[v] = componentcount;
+                stack.push(frame.v);
+                sccs.push(stack[start..].to_vec());
+                stack.truncate(start);
+                index -= indexadjustment;
+                componentcount -= 1;
+            } else {
+                stack.push(frame.v);
+            }
+
+            // return to the caller
+            if let Some(parent) = frames.last_mut() {
+                let p = g.to_index(parent.v);
+                if rootindex[v] < rootindex[p] {
+                    rootindex[p] = rootindex[v];
+                    parent.is_local_root = false;
+                }
+            }
+        }
+    }
+    sccs
+}
+
+// State of one (suspended) visit of a node
+struct Frame<G: IntoNeighbors> {
+    v: G::NodeId,
+    neighbors: G::Neighbors,
+    is_local_root: bool,
+}
+
+impl<G: IntoNeighbors> Frame<G> {
+    fn new(g: G, v: G::NodeId) -> Self {
+        Self {
+            v,
+            neighbors: g.neighbors(v),
+            is_local_root: true,
+        }
+    }
+}
+
+/// The number of the strongly connected component of each node
+///
+/// The returned vector is indexed by [NodeIndexable::to_index].
+/// Components are numbered in the order of [tarjan_scc].
+pub(crate) fn component_indices<G>(g: G) -> Vec<usize>
+where
+    G: IntoNodeIdentifiers + IntoNeighbors + NodeIndexable,
+{
+    let mut component = vec![0; g.node_bound()];
+    for (i, nodes) in tarjan_scc(g).into_iter().enumerate() {
+        for n in nodes {
+            component[g.to_index(n)] = i;
+        }
+    }
+    component
+}
+
+/// The strongly connected components that contain at least one cycle
+///
+/// These are all components returned by [tarjan_scc], apart from
+/// single nodes without a self-loop. Skipping these, we don't have to
+/// set up a search for each node of an acyclic graph.
+pub(crate) fn cyclic_components<G>(g: G) -> impl Iterator<Item = Vec<G::NodeId>>
+where
+    G: IntoNodeIdentifiers + IntoNeighbors + NodeIndexable,
+{
+    tarjan_scc(g)
+        .into_iter()
+        .filter(move |component| is_cyclic(g, component))
+}
+
+/// Whether a strongly connected component contains at least one cycle
+pub(crate) fn is_cyclic<G: IntoNeighbors>(
+    g: G,
+    component: &[G::NodeId],
+) -> bool {
+    match component {
+        [n] => g.neighbors(*n).any(|m| m == *n),
+        _ => true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use petgraph::graph::Graph;
+
+    #[test]
+    fn same_as_petgraph() {
+        let g = Graph::<(), ()>::from_edges([
+            (0, 1),
+            (1, 2),
+            (2, 0),
+            (2, 3),
+            (3, 4),
+            (4, 3),
+            (5, 5),
+            (6, 2),
+            (4, 7),
+            (7, 8),
+            (8, 9),
+            (9, 7),
+            (8, 3),
+        ]);
+        assert_eq!(tarjan_scc(&g), petgraph::algo::tarjan_scc(&g));
+    }
+
+    #[test]
+    fn long_path() {
+        const N: u32 = 100_000;
+        let g = Graph::<(), ()>::from_edges((1..N).map(|i| (i - 1, i)));
+        assert_eq!(tarjan_scc(&g).len(), N as usize);
+    }
+}
@@ -0,0 +1,181 @@
+use alloc::collections::VecDeque;
+use alloc::{vec, vec::Vec};
+
+use petgraph::visit::{
+    GraphProp, IntoNeighbors, IntoNodeIdentifiers, NodeIndexable,
+};
+
+/// Find the length of the shortest cycle
+///
+/// For each node we look for the shortest cycle through it with a
+/// breadth-first search. This takes O(V(V + E)) time in the worst
+/// case, but in contrast to enumerating all cycles is never
+/// exponential.
+pub(crate) fn girth<G>(graph: G) -> Option<usize>
+where
+    G: IntoNeighbors + IntoNodeIdentifiers + NodeIndexable + GraphProp,
+{
+    shortest_cycle(graph).map(|cycle| cycle.len())
+}
+
+/// Find a shortest cycle
+///
+/// If there are several shortest cycles, we return the one through
+/// the first node in `graph.node_identifiers()`. Among the shortest
+/// cycles through this node, we choose the first one found in a
+/// breadth-first search following the order of `graph.neighbors()`.
+pub(crate) fn shortest_cycle<G>(graph: G) -> Option<Vec<G::NodeId>>
+where
+    G: IntoNeighbors + IntoNodeIdentifiers + NodeIndexable + GraphProp,
+{
+    let mut search = ShortestCycleSearch::new(graph);
+    let mut shortest: Option<Vec<_>> = None;
+    for s in graph.node_identifiers() {
+        let bound = shortest.as_ref().map(|c| c.len()).unwrap_or(usize::MAX);
+        if let Some(cycle) = search.shortest_cycle_through(s, bound) {
+            let len = cycle.len();
+            shortest = Some(cycle);
+            if len == 1 {
+                break;
+            }
+        }
+    }
+    shortest
+}
+
+struct ShortestCycleSearch<G: IntoNeighbors> {
+    graph: G,
+    // distance from the source and predecessor on a shortest path,
+    // keyed by `NodeIndexable::to_index`
+    dist: Vec<usize>,
+    pred: Vec<Option<G::NodeId>>,
+    visited: Vec<usize>,
+    queue: VecDeque<G::NodeId>,
+}
+
+impl<G> ShortestCycleSearch<G>
+where
+    G: IntoNeighbors + NodeIndexable + GraphProp,
+{
+    fn new(graph: G) -> Self {
+        Self {
+            graph,
+            dist: vec![usize::MAX; graph.node_bound()],
+            pred: vec![None; graph.node_bound()],
+            visited: Vec::new(),
+            queue: VecDeque::new(),
+        }
+    }
+
+    /// Look for the shortest cycle through `s` that is shorter than `bound`
+    fn shortest_cycle_through(
+        &mut self,
+        s: G::NodeId,
+        bound: usize,
+    ) -> Option<Vec<G::NodeId>> {
+        for n in self.visited.drain(..) {
+            self.dist[n] = usize::MAX;
+            self.pred[n] = None;
+        }
+        self.queue.clear();
+        self.visit(s, None);
+        if self.graph.is_directed() {
+            self.shortest_directed_cycle_through(s, bound)
+        } else {
+            self.shortest_undirected_cycle_through(s, bound)
+        }
+    }
+
+    fn shortest_directed_cycle_through(
+        &mut self,
+        s: G::NodeId,
+        bound: usize,
+    ) -> Option<Vec<G::NodeId>> {
+        while let Some(u) = self.queue.pop_front() {
+            // length of the cycle if there is an edge from `u` to `s`,
+            // and the distance of all newly discovered nodes
+            let d = self.dist[self.graph.to_index(u)] + 1;
+            if d >= bound {
+                break;
+            }
+            for w in self.graph.neighbors(u) {
+                if w == s {
+                    return Some(self.path_to(u));
+                }
+                if self.dist[self.graph.to_index(w)] == usize::MAX {
+                    self.visit(w, Some(u));
+                }
+            }
+        }
+        None
+    }
+
+    // In an undirected graph, each edge between two nodes that are
+    // not predecessors of one another closes a cycle. This cycle does
+    // not have to pass through `s`, in which case it contains
+    // duplicate nodes. However, in this case there is a shorter cycle
+    // that we find when starting from one of its nodes.
+    fn shortest_undirected_cycle_through(
+        &mut self,
+        s: G::NodeId,
+        mut bound: usize,
+    ) -> Option<Vec<G::NodeId>> {
+        let mut closing_edge = None;
+        while let Some(u) = self.queue.pop_front() {
+            let du = self.dist[self.graph.to_index(u)];
+            // any edge to a node that is closer to `s` has already
+            // been considered from the other side
+            if 2 * du + 1 >= bound {
+                break;
+            }
+            for w in self.graph.neighbors(u) {
+                if w == u {
+                    if u == s {
+                        return Some(vec![s]);
+                    }
+                    continue;
+                }
+                // parallel edges don't form cycles
+                if Some(w) == self.pred[self.graph.to_index(u)]
+                    || Some(u) == self.pred[self.graph.to_index(w)]
+                {
+                    continue;
+                }
+                let dw = self.dist[self.graph.to_index(w)];
+                if dw == usize::MAX {
+                    self.visit(w, Some(u));
+                } else if du + dw + 1 < bound {
+                    bound = du + dw + 1;
+                    closing_edge = Some((u, w));
+                }
+            }
+        }
+        let (u, w) = closing_edge?;
+        let mut cycle = self.path_to(u);
+        let back = self.path_to(w);
+        cycle.extend(back[1..].iter().rev());
+        Some(cycle)
+    }
+
+    /// The nodes on the shortest path from the last source to `n`
+    fn path_to(&self, mut n: G::NodeId) -> Vec<G::NodeId> {
+        let mut path = vec![n];
+        while let Some(pred) = self.pred[self.graph.to_index(n)] {
+            path.push(pred);
+            n = pred;
+        }
+        path.reverse();
+        path
+    }
+
+    fn visit(&mut self, n: G::NodeId, pred: Option<G::NodeId>) {
+        let idx = self.graph.to_index(n);
+        self.dist[idx] = match pred {
+            Some(pred) => self.dist[self.graph.to_index(pred)] + 1,
+            None => 0,
+        };
+        self.pred[idx] = pred;
+        self.visited.push(idx);
+        self.queue.push_back(n);
+    }
+}
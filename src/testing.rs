@@ -0,0 +1,243 @@
+//! Graph generators and reference implementations for tests and
+//! benchmarks
+//!
+//! Apart from the proptest strategies, all graphs are generated from
+//! fixed seeds, so they are the same in every run. The brute-force
+//! searches check all paths and are only feasible for small graphs.
+//! This module requires the `testing` feature.
+use alloc::{vec, vec::Vec};
+
+use petgraph::graph::{EdgeIndex, Graph, NodeIndex};
+use petgraph::visit::EdgeRef;
+use petgraph::{Directed, EdgeType, Undirected};
+use proptest::strategy::Strategy;
+
+// xorshift pseudo-random numbers
+fn rng(mut seed: u64) -> impl FnMut() -> u64 {
+    move || {
+        seed ^= seed << 13;
+        seed ^= seed >> 7;
+        seed ^= seed << 17;
+        seed
+    }
+}
+
+/// `num` random graphs with at most `max_nodes` nodes and `max_edges`
+/// edges each
+///
+/// Each graph has at least one node. The edges may include
+/// self-loops and parallel edges.
+pub fn random_graphs(
+    num: usize,
+    max_nodes: u64,
+    max_edges: u64,
+) -> Vec<Graph<(), ()>> {
+    let mut rand = rng(0x2545_f491_4f6c_dd1d);
+    let mut rand = move |max: u64| rand() % max;
+    (0..num)
+        .map(|_| {
+            let num_nodes = rand(max_nodes) + 1;
+            let num_edges = rand(max_edges + 1);
+            let mut g = Graph::new();
+            for _ in 0..num_nodes {
+                g.add_node(());
+            }
+            for _ in 0..num_edges {
+                let from = rand(num_nodes) as u32;
+                let to = rand(num_nodes) as u32;
+                g.add_edge(from.into(), to.into(), ());
+            }
+            g
+        })
+        .collect()
+}
+
+/// Random graph with `num_edges` edges between `num_nodes` nodes
+pub fn random_graph(num_nodes: u32, num_edges: usize) -> Graph<(), ()> {
+    let mut rand = rng(0x2545_f491_4f6c_dd1d);
+    let mut g = Graph::with_capacity(num_nodes as usize, num_edges);
+    for _ in 0..num_nodes {
+        g.add_node(());
+    }
+    for _ in 0..num_edges {
+        let from = (rand() % num_nodes as u64) as u32;
+        let to = (rand() % num_nodes as u64) as u32;
+        g.add_edge(from.into(), to.into(), ());
+    }
+    g
+}
+
+/// Strongly connected graph where each edge is present with
+/// probability `1 / inv_density`
+pub fn dense_random_graph(num_nodes: u32, inv_density: u64) -> Graph<(), ()> {
+    let mut rand = rng(0x9e37_79b9_7f4a_7c15);
+    let mut g = Graph::from_edges(
+        (0..num_nodes)
+            .flat_map(|i| {
+                (0..num_nodes)
+                    .filter(|&j| j != i)
+                    .map(move |j| (i, j))
+                    .collect::<Vec<_>>()
+            })
+            .filter(|_| rand().is_multiple_of(inv_density)),
+    );
+    // make sure there is a single component
+    for i in 0..num_nodes {
+        g.update_edge(i.into(), ((i + 1) % num_nodes).into(), ());
+    }
+    g
+}
+
+/// `num` complete components with `size` nodes each, so the search
+/// never gets deep
+///
+/// The small components are preceded by a ring with `large` nodes,
+/// which is searched first. This checks that the buffers sized for a
+/// large component do not slow down the search in later small ones.
+/// For `large = 0`, there is no ring.
+pub fn many_small_components(num: u32, size: u32, large: u32) -> Graph<(), ()> {
+    let ring = (0..large).map(move |i| (i, (i + 1) % large));
+    let small = (0..num).flat_map(move |c| {
+        let offset = large + c * size;
+        (0..size).flat_map(move |i| {
+            (0..size)
+                .filter(move |&j| j != i)
+                .map(move |j| (offset + i, offset + j))
+        })
+    });
+    Graph::from_edges(ring.chain(small))
+}
+
+/// A long ring with a few shortcuts, so the search gets very deep
+pub fn deep_component(num_nodes: u32) -> Graph<(), ()> {
+    let ring = (0..num_nodes).map(|i| (i, (i + 1) % num_nodes));
+    let shortcuts = (0..num_nodes)
+        .step_by(500)
+        .map(|i| (i, (i + 2) % num_nodes));
+    Graph::from_edges(ring.chain(shortcuts))
+}
+
+/// Small directed graphs for property tests
+///
+/// The graphs have between one and seven nodes and up to 20 edges,
+/// including self-loops and parallel edges. They shrink towards fewer
+/// nodes and edges.
+pub fn arb_graph() -> impl Strategy<Value = Graph<(), (), Directed>> {
+    arb_graph_with()
+}
+
+/// Small undirected graphs for property tests
+///
+/// Like [arb_graph], but with undirected edges.
+pub fn arb_ungraph() -> impl Strategy<Value = Graph<(), (), Undirected>> {
+    arb_graph_with()
+}
+
+fn arb_graph_with<Ty: EdgeType>() -> impl Strategy<Value = Graph<(), (), Ty>> {
+    use proptest::{collection::vec, prelude::*};
+
+    (1..8u32).prop_flat_map(|num_nodes| {
+        vec((0..num_nodes, 0..num_nodes), 0..20).prop_map(move |edges| {
+            let mut g = Graph::with_capacity(num_nodes as usize, 0);
+            for _ in 0..num_nodes {
+                g.add_node(());
+            }
+            g.extend_with_edges(edges);
+            g
+        })
+    })
+}
+
+/// Find all cycles by checking all paths
+///
+/// Each cycle starts with its smallest node. In undirected graphs,
+/// it is oriented such that the second node is smaller than the last
+/// one, and cycles with two nodes are not included. The cycles are
+/// sorted. This is the same as the sorted output of
+/// [cycles_canonical](crate::Cycles::cycles_canonical).
+pub fn brute_force_cycles<N, E, Ty: EdgeType>(
+    g: &Graph<N, E, Ty>,
+) -> Vec<Vec<NodeIndex>> {
+    fn extend<N, E, Ty: EdgeType>(
+        g: &Graph<N, E, Ty>,
+        path: &mut Vec<NodeIndex>,
+        cycles: &mut Vec<Vec<NodeIndex>>,
+    ) {
+        let mut neighbors: Vec<_> =
+            g.neighbors(*path.last().unwrap()).collect();
+        neighbors.sort();
+        neighbors.dedup();
+        for n in neighbors {
+            if n == path[0] {
+                // going back and forth is not an undirected cycle
+                if g.is_directed() || path.len() != 2 {
+                    cycles.push(path.clone());
+                }
+            } else if n > path[0] && !path.contains(&n) {
+                path.push(n);
+                extend(g, path, cycles);
+                path.pop();
+            }
+        }
+    }
+    let mut cycles = Vec::new();
+    for n in g.node_indices() {
+        extend(g, &mut vec![n], &mut cycles);
+    }
+    if !g.is_directed() {
+        // each cycle was found in both orientations
+        for cycle in &mut cycles {
+            if cycle.len() > 2 && cycle[1] > cycle[cycle.len() - 1] {
+                cycle[1..].reverse();
+            }
+        }
+    }
+    cycles.sort();
+    cycles.dedup();
+    cycles
+}
+
+/// Find all cycles in terms of edges by checking all paths
+///
+/// Each cycle is given by its set of edges, sorted by index. The
+/// cycles are sorted as well. In undirected graphs, two distinct
+/// edges between the same nodes form a cycle.
+pub fn brute_force_cycles_edges<N, E, Ty: EdgeType>(
+    g: &Graph<N, E, Ty>,
+) -> Vec<Vec<EdgeIndex>> {
+    fn extend<N, E, Ty: EdgeType>(
+        g: &Graph<N, E, Ty>,
+        start: NodeIndex,
+        nodes: &mut Vec<NodeIndex>,
+        edges: &mut Vec<EdgeIndex>,
+        cycles: &mut Vec<Vec<EdgeIndex>>,
+    ) {
+        let last = *nodes.last().unwrap();
+        for e in g.edges(last) {
+            // in undirected graphs, an edge must not be traversed twice
+            if edges.contains(&e.id()) {
+                continue;
+            }
+            let n = e.target();
+            if n == start {
+                let mut cycle = edges.clone();
+                cycle.push(e.id());
+                cycle.sort();
+                cycles.push(cycle);
+            } else if n > start && !nodes.contains(&n) {
+                nodes.push(n);
+                edges.push(e.id());
+                extend(g, start, nodes, edges, cycles);
+                edges.pop();
+                nodes.pop();
+            }
+        }
+    }
+    let mut cycles = Vec::new();
+    for n in g.node_indices() {
+        extend(g, n, &mut vec![n], &mut Vec::new(), &mut cycles);
+    }
+    cycles.sort();
+    cycles.dedup();
+    cycles
+}
@@ -0,0 +1,51 @@
+use alloc::{vec, vec::Vec};
+
+use petgraph::visit::{
+    GraphProp, IntoNeighbors, IntoNodeIdentifiers, NodeIndexable,
+};
+
+/// Apply `f` to each cycle consisting of three nodes
+///
+/// Instead of a general cycle search, we look for a common neighbour
+/// for each pair of adjacent nodes. In directed graphs, each triangle
+/// `[a, b, c]` corresponds to the edges `a -> b -> c -> a`.
+pub(crate) fn visit_triangles<G, F>(graph: G, mut f: F)
+where
+    G: IntoNeighbors + IntoNodeIdentifiers + NodeIndexable + GraphProp,
+    F: FnMut([G::NodeId; 3]),
+{
+    // sorted neighbours without duplicates or self-loops, keyed by
+    // `NodeIndexable::to_index`
+    let mut neighbors = vec![Vec::new(); graph.node_bound()];
+    for n in graph.node_identifiers() {
+        let u = graph.to_index(n);
+        let neighbors = &mut neighbors[u];
+        neighbors.extend(
+            graph
+                .neighbors(n)
+                .map(|n| graph.to_index(n))
+                .filter(|&v| v != u),
+        );
+        neighbors.sort_unstable();
+        neighbors.dedup();
+    }
+    // Each triangle is found starting from its smallest node. In
+    // undirected graphs, we additionally demand that the second node
+    // is smaller than the third one, so that each triangle is only
+    // found in one orientation.
+    let directed = graph.is_directed();
+    for (u, out) in neighbors.iter().enumerate() {
+        for &v in out.iter().filter(|&&v| v > u) {
+            let min_w = if directed { u } else { v };
+            for &w in neighbors[v].iter().filter(|&&w| w > min_w && w != v) {
+                if neighbors[w].binary_search(&u).is_ok() {
+                    f([
+                        graph.from_index(u),
+                        graph.from_index(v),
+                        graph.from_index(w),
+                    ]);
+                }
+            }
+        }
+    }
+}